@@ -1,16 +1,18 @@
 use attribute_derive::{FlagOrValue, FromAttr};
 use manyhow::{bail, ensure, Result};
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{format_ident, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::token::{Brace, Paren};
 use syn::{
-    AssocType, Attribute, Expr, FnArg, GenericArgument, Generics, Ident, Lifetime, Pat, PatIdent,
-    PatTupleStruct, PatType, PathArguments, ReturnType, Token, Type, TypeImplTrait, TypeParamBound,
-    Visibility,
+    AssocType, Attribute, ConstParam, Expr, FnArg, GenericArgument, GenericParam, Generics, Ident,
+    Lifetime, LifetimeParam, LitStr, Pat, PatIdent, PatTupleStruct, PatType, PathArguments,
+    ReturnType, Token, Type, TypeImplTrait, TypeParam, TypeParamBound, Visibility, WherePredicate,
 };
 use syn_derive::ToTokens;
 
+use crate::htmx::{is_keyword, is_valid_tag_name};
 use crate::*;
 
 enum Arg {
@@ -21,10 +23,14 @@ enum Arg {
 #[derive(Debug)]
 struct Field {
     name: Ident,
+    /// Name used in `html!`, i.e., of the setter.
+    prop: Ident,
     ty: Type,
     pat: Pat,
     default: FlagOrValue<Expr>,
     default_type: Option<Type>,
+    into: Option<bool>,
+    vis: Visibility,
     doc_attrs: TokenStream,
 }
 
@@ -46,6 +52,14 @@ impl Field {
         &self.name
     }
 
+    fn setter(&self) -> Ident {
+        if is_keyword(&self.prop) {
+            format_ident!("{}_", self.prop.unraw(), span = self.prop.span())
+        } else {
+            self.prop.clone()
+        }
+    }
+
     fn unset(&self) -> TokenStream {
         if let Some(default_type) = &self.default_type {
             return quote!(#default_type);
@@ -99,12 +113,18 @@ impl Field {
         match &self.default {
             FlagOrValue::Value(default) => quote!(let #pat = #name.get_or_else(|| #default);),
             _ if self.is_impl_trait() && self.is_optional() => quote! {},
-            _ if self.is_impl_trait() => quote!(let ::htmx::__private::Set(#name) = #name;),
             _ if self.is_optional() => quote!(let #pat = #name.get_or_default();),
-            _ => quote!(let ::htmx::__private::Set(#name) = #name;),
+            _ => {
+                let required = self.required_trait();
+                quote!(let #name = #required::into_value(#name);)
+            }
         }
     }
 
+    fn required_trait(&self) -> Ident {
+        format_ident!("{}_is_required", self.name)
+    }
+
     fn get_generics(&self, base: &Ident) -> Option<TokenStream> {
         if let Type::ImplTrait(ty) = &self.ty {
             let mut tokens = TokenStream::new();
@@ -147,12 +167,42 @@ fn desugar_impl(tokens: &mut TokenStream, ty: TypeImplTrait, base: &Ident) {
     tokens.extend(quote!(#base: #bounds));
 }
 
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(t) if t.elems.is_empty())
+}
+
+/// Returns `E` for `Result<(), E>`.
+fn result_error(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Type(ok), GenericArgument::Type(error)] if is_unit(ok) => Some(error),
+        _ => None,
+    }
+}
+
+fn contains_ident(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&&ident),
+        TokenTree::Group(group) => contains_ident(group.stream(), idents),
+        _ => false,
+    })
+}
+
 impl TryFrom<FnArg> for Arg {
     type Error = manyhow::Error;
 
     fn try_from(arg: FnArg) -> std::result::Result<Self, Self::Error> {
         // hi
-        ensure!(let FnArg::Typed(PatType { mut attrs, pat, ty, .. }) = arg,
+        ensure!(let FnArg::Typed(PatType { mut attrs, pat, mut ty, .. }) = arg,
                 arg, "`self` is not supported");
 
         let ident = match &*pat {
@@ -178,12 +228,42 @@ impl TryFrom<FnArg> for Arg {
 
         let DefaultAttr(mut default) = DefaultAttr::remove_attributes(&mut attrs)?;
         let DefaultType(default_type) = DefaultType::remove_attributes(&mut attrs)?;
+        let PropAttr { into, vis, name } = PropAttr::remove_attributes(&mut attrs)?;
+        let vis = vis.map_or_else(|| Ok(parse_quote!(pub)), |vis| vis.parse())?;
+        let prop = if let Some(name) = name {
+            let Ok(prop) = name.parse_with(Ident::parse_any) else {
+                bail!(
+                    name, "prop names need to be identifiers";
+                    note = "`-` separated names are set via `custom_attr`"
+                );
+            };
+            prop
+        } else {
+            ident.clone()
+        };
         // let ChildrenAttr(children) = ChildrenAttr::remove_attributes(attrs)?;
 
         if default_type.is_some() && default.is_none() {
             default = FlagOrValue::Flag;
         }
 
+        // Slots are optional and can borrow for `'html`.
+        let slot = attrs.iter().position(|a| a.path().is_ident("slot"));
+        if let Some(slot) = slot {
+            let slot = attrs.remove(slot);
+            slot.meta.require_path_only()?;
+            let Type::ImplTrait(ty) = &mut *ty else {
+                bail!(ty, "slots need to be `impl IntoHtml`");
+            };
+            ty.bounds.push(TypeParamBound::Lifetime(Lifetime::new(
+                "'html",
+                Span::call_site(),
+            )));
+            if default.is_none() {
+                default = FlagOrValue::Flag;
+            }
+        }
+
         let doc_attrs = attrs
             .into_iter()
             .filter(|a| a.path().is_ident("doc"))
@@ -192,10 +272,13 @@ impl TryFrom<FnArg> for Arg {
 
         Ok(Arg::Field(Field {
             name: ident.clone(),
+            prop,
             pat: *pat,
             ty: *ty,
             default,
             default_type,
+            into,
+            vis,
             doc_attrs,
         }))
         // Ok((quote!(#(#attrs)* pub #ident: #ty,), quote!(#ident: #pat,)))
@@ -224,7 +307,7 @@ pub struct Component {
 }
 
 pub fn component(
-    _input: TokenStream,
+    input: TokenStream,
     Component {
         attrs,
         vis,
@@ -236,18 +319,32 @@ pub fn component(
         ..
     }: Component,
 ) -> Result {
-    ensure!(generics.params.is_empty(), "generics are not supported");
-    if let ReturnType::Type(_, t) = &output {
-        if let Type::Tuple(t) = &**t {
-            if !t.elems.is_empty() {
-                bail!(output, "expected `()` return type");
-            }
-        } else {
-            bail!(output, "expected `()` return type");
-        }
+    let ComponentAttr { tag } = ComponentAttr::from_input(input)?;
+    if let Some(tag) = &tag {
+        let name = tag.value();
+        ensure!(
+            !name.is_empty() && is_valid_tag_name(&name),
+            tag,
+            "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
+        );
+        ensure!(
+            !VOID_ELEMENTS.contains(&name.to_ascii_lowercase().as_str()),
+            tag,
+            "void element `{name}` cannot wrap the component's output"
+        );
     }
+    let error = match &output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, t) if is_unit(t) => None,
+        ReturnType::Type(_, t) => {
+            let Some(error) = result_error(t) else {
+                bail!(output, "expected `()` or `Result<(), E>` return type");
+            };
+            Some(error)
+        }
+    };
 
-    let (body, args) = inputs.into_iter().map(Arg::try_from).try_fold(
+    let (body, mut args) = inputs.into_iter().map(Arg::try_from).try_fold(
         Default::default(),
         |mut acc, arg| -> Result<(Option<Ident>, Vec<Field>)> {
             match arg? {
@@ -263,10 +360,68 @@ pub fn component(
 
     let body = body.unwrap_or_else(|| Ident::new("body", Span::call_site()));
 
+    // `Into` would prevent inferring the function's type parameters.
+    let type_params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    for field in &mut args {
+        if contains_ident(field.ty.to_token_stream(), &type_params) {
+            field.into.get_or_insert(false);
+        }
+    }
+
     let html_lt = Lifetime::new("'html", Span::call_site());
 
+    // The function's generics are placed between `'html` and the fields'
+    // generics, lifetimes need to come first.
+    let mut user_params: Vec<_> = generics.params.iter().cloned().collect();
+    user_params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+    let mut user_phantom = TokenStream::new();
+    let mut user_args = TokenStream::new();
+    let mut outlives_html = vec![];
+    for param in &mut user_params {
+        match param {
+            GenericParam::Lifetime(LifetimeParam { lifetime, .. }) => {
+                user_phantom.extend(quote!(&#lifetime (),));
+                user_args.extend(quote!(#lifetime,));
+                outlives_html.push(quote!(#lifetime: #html_lt));
+            }
+            GenericParam::Type(TypeParam {
+                ident,
+                default,
+                eq_token,
+                ..
+            }) => {
+                *default = None;
+                *eq_token = None;
+                user_phantom.extend(quote!(fn() -> #ident,));
+                user_args.extend(quote!(#ident,));
+                outlives_html.push(quote!(#ident: #html_lt));
+            }
+            GenericParam::Const(ConstParam {
+                ident,
+                default,
+                eq_token,
+                ..
+            }) => {
+                *default = None;
+                *eq_token = None;
+                user_args.extend(quote!(#ident,));
+            }
+        }
+    }
+    let user_params = quote!(#(#user_params,)*);
+    let where_clause = &generics.where_clause;
+    let body_where_clause = {
+        let mut generics = generics.clone();
+        generics.make_where_clause().predicates.extend(
+            outlives_html
+                .into_iter()
+                .map(|predicate| -> WherePredicate { parse_quote!(#predicate) }),
+        );
+        generics.where_clause
+    };
+
     let fields = args.iter().map(Field::field);
-    let generics = args.iter().map(Field::generic);
+    let generics: Vec<_> = args.iter().map(Field::generic).collect();
     let unsets_types: Vec<_> = args.iter().map(Field::unset).collect();
     let unset_values: Vec<_> = args.iter().map(Field::unset_value).collect();
     let field_names: Vec<_> = args.iter().map(Field::name).collect();
@@ -279,22 +434,126 @@ pub fn component(
             let ty = &f.ty;
             quote!(#g: ::htmx::__private::Settable<#ty>)
         })
-        .chain(args.iter().filter_map(|f| f.get_generics(&f.generic())));
-
-    let mandatory_gens = args.iter().map(|f| {
-        if f.is_optional() {
-            f.generic().into_token_stream()
-        } else if f.is_impl_trait() {
-            let gen = f.generic();
-            quote!(::htmx::__private::Set<#gen>)
+        .chain(
+            args.iter()
+                .filter(|f| f.is_optional())
+                .filter_map(|f| f.get_generics(&f.generic())),
+        )
+        .chain(
+            args.iter()
+                .filter(|f| !f.is_optional())
+                .map(|f| f.generic().into_token_stream()),
+        );
+
+    // Missing required props are reported through `#[diagnostic::on_unimplemented]`
+    // on a trait per prop, this requires the bounds to be on the methods.
+    let mut required_traits = vec![];
+    let mut required_gens = vec![];
+    let mut required_bounds = vec![];
+    for field in args.iter().filter(|f| !f.is_optional()) {
+        let name = field.prop.unraw();
+        let gen = field.generic();
+        let required = field.required_trait();
+        let message = format!("required prop `{name}` not set on component `{struct_name}`");
+        let label = format!("missing `{name}`");
+        let note = format!("set it via `{name}=...`");
+        required_traits.push(quote! {
+            #[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+            #[allow(non_camel_case_types)]
+            pub trait #required {
+                type Value;
+                fn into_value(self) -> Self::Value;
+            }
+            impl<T> #required for ::htmx::__private::Set<T> {
+                type Value = T;
+                fn into_value(self) -> T {
+                    self.0
+                }
+            }
+        });
+        if let Some(bounds) = field.get_generics(&format_ident!("{gen}Value")) {
+            let value = format_ident!("{gen}Value");
+            required_gens.push(bounds);
+            required_bounds.push(quote!(#gen: #required<Value = #value>, #value: #html_lt));
         } else {
-            let ty = &f.ty;
-            quote!(::htmx::__private::Set<#ty>)
+            let ty = &field.ty;
+            required_bounds.push(quote!(#gen: #required<Value = #ty>));
         }
-    });
+    }
 
     let field_destructure = args.iter().map(Field::destructure);
 
+    // Components with a `tag` forward custom attributes to that element.
+    let forwarded = tag.as_ref().map(|_| quote!(__attrs,));
+    let forwarded_field = tag
+        .as_ref()
+        .map(|_| quote!(__attrs: ::htmx::__private::Attrs,));
+    let forwarded_init = tag
+        .as_ref()
+        .map(|_| quote!(__attrs: ::core::default::Default::default(),));
+    let forwarded_fns = tag.as_ref().map(|_| {
+        // Props take precedence over forwarded attributes with the same name.
+        let class = (!args.iter().any(|prop| prop.setter() == "class")).then(|| {
+            quote! {
+                /// Adds classes to the wrapping element.
+                pub fn class(mut self, value: impl ::htmx::attributes::ToAttribute<::htmx::attributes::Class>) -> Self {
+                    self.__attrs.class(value);
+                    self
+                }
+            }
+        });
+        quote! {
+            impl<#html_lt, #user_params #(#generics),*> #struct_name<#html_lt, #user_args #(#generics),*> #where_clause {
+                #class
+
+                /// Sets the attribute `key` on the wrapping element.
+                pub fn custom_attr(
+                    mut self,
+                    key: impl ::core::fmt::Display,
+                    value: impl ::htmx::attributes::AttributeValue,
+                ) -> Self {
+                    self.__attrs.push(key, value);
+                    self
+                }
+
+                /// Sets the attribute `key` on the wrapping element.
+                pub fn custom_attr_unchecked(
+                    self,
+                    key: impl ::core::fmt::Display,
+                    value: impl ::htmx::attributes::AttributeValue,
+                ) -> Self {
+                    self.custom_attr(key, value)
+                }
+            }
+
+            // Global attributes of native elements, e.g., `id` or `onclick`.
+            impl<#html_lt, #user_params #(#generics),*> ::htmx::__private::ForwardAttrs for #struct_name<#html_lt, #user_args #(#generics),*> #where_clause {
+                fn forward_attr(mut self, write: impl FnOnce(&mut ::htmx::Html)) -> Self {
+                    self.__attrs.write(write);
+                    self
+                }
+            }
+        }
+    });
+    // Errors are rendered in place of the component's output.
+    let fn_body = if let Some(error) = error {
+        quote! {
+            match (|| -> ::core::result::Result<_, #error> {#fn_body})() {
+                ::core::result::Result::Ok(__output) => ::htmx::IntoHtml::into_html(__output, __html),
+                ::core::result::Result::Err(__error) => ::htmx::IntoHtml::into_html(__error, __html),
+            }
+        }
+    } else {
+        quote!((||{#fn_body})().into_html(__html))
+    };
+    let fn_body = if let Some(tag) = &tag {
+        quote! {
+            ::htmx::__private::tagged(__html, #tag, &__attrs, ::htmx::Fragment(|__html: &mut ::htmx::Html| #fn_body))
+        }
+    } else {
+        fn_body
+    };
+
     let mut setters = vec![];
     for i in 0..args.len() {
         let mut impl_gens = vec![];
@@ -306,6 +565,7 @@ pub fn component(
         let field @ Field {
             name: field_name,
             doc_attrs,
+            vis,
             ..
         } = &args[i];
         let gen = field.generic();
@@ -321,10 +581,13 @@ pub fn component(
                     fn_gen = Some(quote!(#bounds));
                     set_gens.push(quote!(::htmx::__private::Set<#generic>));
                     structure.push(quote!(#name: ::htmx::__private::Set(#name)));
-                } else {
+                } else if field.into.unwrap_or(true) {
                     fn_gen = Some(quote!(#gen: Into<#ty>));
                     set_gens.push(quote!(::htmx::__private::Set<#ty>));
                     structure.push(quote!(#name: ::htmx::__private::Set(#name.into())));
+                } else {
+                    set_gens.push(quote!(::htmx::__private::Set<#ty>));
+                    structure.push(quote!(#name: ::htmx::__private::Set(#name)));
                 };
             } else {
                 impl_gens.push(quote!(#generic));
@@ -335,22 +598,31 @@ pub fn component(
             }
         }
 
-        let already_set_msg = format!("{field_name} was alredy set");
+        let setter = field.setter();
+        let already_set_msg = format!("{} was alredy set", field.prop.unraw());
         let already_set_ty = format_ident!("{field_name}_was_alredy_set");
 
         let extra_gen = field.is_impl_trait().then_some(&gen).into_iter();
 
+        let param_ty = if fn_gen.is_some() {
+            gen.to_token_stream()
+        } else {
+            field.ty.to_token_stream()
+        };
+
         setters.push(quote! {
-          impl<#html_lt, #(#impl_gens),*> #struct_name<#html_lt, #(#unset_gens),*> {
+          impl<#html_lt, #user_params #(#impl_gens),*> #struct_name<#html_lt, #user_args #(#unset_gens),*> #where_clause {
               #doc_attrs
-              pub fn #field_name<#fn_gen>(self, #field_name: #gen)
-                  -> #struct_name<#html_lt, #(#set_gens),*> {
+              #vis fn #setter<#fn_gen>(self, #field_name: #param_ty)
+                  -> #struct_name<#html_lt, #user_args #(#set_gens),*> {
                   let Self {
                       html,
+                      #forwarded
                       #(#destructure),*
                   } = self;
                   #struct_name {
                       html,
+                      #forwarded
                       #(#structure),*
                   }
               }
@@ -359,11 +631,11 @@ pub fn component(
           #[allow(non_camel_case_types)]
           pub struct #already_set_ty;
 
-          impl<#html_lt, #(#extra_gen,)* #(#impl_gens),*> #struct_name<#html_lt, #(#set_gens),*> {
+          impl<#html_lt, #user_params #(#extra_gen,)* #(#impl_gens),*> #struct_name<#html_lt, #user_args #(#set_gens),*> #where_clause {
               #[doc(hidden)]
               #[deprecated = #already_set_msg]
               #[allow(unused)]
-              pub fn #field_name<__Gen>(
+              #vis fn #setter<__Gen>(
                   self,
                   #field_name: __Gen, _: #already_set_ty
               ) -> Self {
@@ -379,37 +651,52 @@ pub fn component(
 
         #(#attrs)*
         #[must_use = "call body or close"]
-        #vis struct #struct_name<#html_lt, #(#generics),*> {
-            html: ::core::marker::PhantomData<&#html_lt ()>,
+        #vis struct #struct_name<#html_lt, #user_params #(#generics),*> #where_clause {
+            html: ::core::marker::PhantomData<(&#html_lt (), #user_phantom)>,
+            #forwarded_field
             #(#fields),*
         }
         const _: () = {
             use ::core::default::Default as _;
-            impl<#html_lt> #struct_name<#html_lt, #(#unsets_types),*> {
+            impl<#html_lt, #user_params> #struct_name<#html_lt, #user_args #(#unsets_types),*> #where_clause {
                 pub fn new(_: &mut ::htmx::Html) -> Self {
                     Self {
                         html: ::core::marker::PhantomData,
+                        #forwarded_init
                         #(#field_names: #unset_values),*
                     }
                 }
+
+                // Allows `Unused::unused` on closing tags to infer the generics.
+                #[doc(hidden)]
+                pub fn unused() {}
             }
 
             #(#setters)*
 
-            impl<#html_lt, #(#optional_gens),*> #struct_name<#html_lt, #(#mandatory_gens),*> {
-                pub fn body(self, #body: impl ::htmx::IntoHtml + #html_lt) -> impl ::htmx::IntoHtml + #html_lt {
+            #forwarded_fns
+
+            #(#required_traits)*
+
+            impl<#html_lt, #user_params #(#optional_gens),*> #struct_name<#html_lt, #user_args #(#generics),*> #body_where_clause {
+                pub fn body<#(#required_gens),*>(self, #body: impl ::htmx::IntoHtml + #html_lt) -> impl ::htmx::IntoHtml + #html_lt
+                where #(#required_bounds),*
+                {
                     let Self {
                         html: _,
+                        #forwarded
                         #(#field_names),*
                     } = self;
 
                     #(#field_destructure;)*
 
 
-                    ::htmx::Fragment(move |__html: &mut ::htmx::Html|(||{#fn_body})().into_html(__html))
+                    ::htmx::Fragment(move |__html: &mut ::htmx::Html| #fn_body)
                 }
 
-                pub fn close(self)  -> impl ::htmx::IntoHtml + #html_lt {
+                pub fn close<#(#required_gens),*>(self) -> impl ::htmx::IntoHtml + #html_lt
+                where #(#required_bounds),*
+                {
                     self.body(::htmx::Fragment::EMPTY)
                 }
             }
@@ -417,6 +704,31 @@ pub fn component(
     })
 }
 
+/// Elements that cannot have children, i.e., cannot be used as `tag`.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+#[derive(FromAttr)]
+#[attribute(ident = component)]
+struct ComponentAttr {
+    /// Element wrapping the component's output.
+    tag: Option<LitStr>,
+}
+
+#[derive(FromAttr)]
+#[attribute(ident = prop)]
+struct PropAttr {
+    /// Whether the setter accepts `impl Into<T>`, defaults to `true`.
+    into: Option<bool>,
+    /// Visibility of the setter, defaults to `pub`.
+    vis: Option<LitStr>,
+    /// Name of the prop, defaults to the argument's name, keywords are
+    /// allowed, e.g., `type`.
+    name: Option<LitStr>,
+}
+
 #[derive(FromAttr)]
 #[attribute(ident = default)]
 struct DefaultAttr(FlagOrValue<Expr>);
@@ -424,3 +736,34 @@ struct DefaultAttr(FlagOrValue<Expr>);
 #[derive(FromAttr)]
 #[attribute(ident = default_type)]
 struct DefaultType(Option<Type>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prop_visibility() {
+        let output = component(TokenStream::new(), parse_quote! {
+            fn Component(
+                #[doc(hidden)]
+                #[prop(vis = "pub(crate)")]
+                internal: bool,
+            ) {}
+        })
+        .unwrap()
+        .to_string();
+        assert!(output.contains(&quote!(#[doc(hidden)] pub(crate) fn internal).to_string()));
+    }
+
+    #[test]
+    fn tag_validation() {
+        for (tag, message) in [
+            (quote!(tag = "img"), "void element `img`"),
+            (quote!(tag = "my element"), "invalid tag name `my element`"),
+        ] {
+            let item = parse_quote! { fn Component() {} };
+            let error = format!("{:?}", component(tag, item).unwrap_err());
+            assert!(error.contains(message), "{error}");
+        }
+    }
+}
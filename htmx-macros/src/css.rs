@@ -1,25 +1,16 @@
-#![allow(unused)]
-use manyhow::bail;
-use proc_macro2::Group;
+use manyhow::{bail, ensure};
+use proc_macro2::{Delimiter, Spacing, TokenTree};
 use proc_macro_utils::TokenStream2Ext;
 use quote::ToTokens;
 
 use crate::*;
 
-// TODO this is not good enough, we need to fully parse css due to idents
-// containing `-` that should not have spaces and expressions that need them.
 pub fn css(input: TokenStream) -> Result<TokenStream> {
-    let output = css_transform(input)?;
-    let string = output.to_string();
-    Ok(quote!(htmx::Css(string.into())))
-}
-
-pub fn css_transform(input: TokenStream) -> Result<TokenStream> {
-    let mut output = TokenStream::new();
+    let mut tokens = Vec::new();
     let mut input = input.parser();
     while !input.is_empty() {
         if let Some(use_) = input.next_keyword("use") {
-            let Some(path) = input.next_string() else {
+            let Some(_path) = input.next_string() else {
                 if let Some(unexp) = input.next() {
                     bail!(unexp, "expected string path");
                 } else {
@@ -33,21 +24,560 @@ pub fn css_transform(input: TokenStream) -> Result<TokenStream> {
                     bail!(use_, "expected to be followed by string path");
                 }
             }
-        } else if let Some(group) = input.next_group() {
-            output.push(Group::new(group.delimiter(), css(group.stream())?).into())
         } else {
-            output.extend(input.next())
+            tokens.extend(input.next());
+        }
+    }
+    let mut output = Output::default();
+    write_items(&mut output, &[], items(&tokens, false)?);
+    Ok(output.into_token_stream())
+}
+
+/// Generated CSS, consisting of string literals and interpolated Rust
+/// expressions.
+#[derive(Default)]
+struct Output {
+    /// Format string, i.e., with escaped `{` and `}`.
+    format: String,
+    args: Vec<TokenStream>,
+}
+
+impl Output {
+    fn push_str(&mut self, s: &str) {
+        self.format
+            .push_str(&s.replace('{', "{{").replace('}', "}}"));
+    }
+
+    fn push_expr(&mut self, expr: TokenStream) {
+        self.format.push_str("{}");
+        self.args.push(expr);
+    }
+
+    fn push_output(&mut self, output: Output) {
+        self.format.push_str(&output.format);
+        self.args.extend(output.args);
+    }
+
+    /// Puts every block on its own line.
+    fn new_line(&mut self) {
+        if !self.format.is_empty() && !self.format.ends_with('\n') {
+            self.push_str("\n");
+        }
+    }
+}
+
+impl ToTokens for Output {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { format, args } = self;
+        if args.is_empty() {
+            let css = format.replace("{{", "{").replace("}}", "}");
+            quote!(::htmx::Css(::std::borrow::Cow::Borrowed(#css)))
+        } else {
+            quote!(::htmx::Css(::std::borrow::Cow::Owned(
+                ::std::format!(#format, #({#args}),*)
+            )))
+        }
+        .to_tokens(tokens);
+    }
+}
+
+enum Item {
+    Declaration(Declaration),
+    Rule(Rule),
+    AtRule(AtRule),
+}
+
+struct Declaration {
+    property: String,
+    value: Output,
+}
+
+/// Style rule, possibly nested, in which case its selectors are relative to
+/// the parent's, i.e., `&` refers to the parent selector, without `&` they
+/// are descendants.
+struct Rule {
+    selectors: Vec<String>,
+    items: Vec<Item>,
+}
+
+/// At-rule, e.g., `@import "reset.css";` or `@media (...) { ... }`.
+struct AtRule {
+    /// Name including the `@`.
+    name: String,
+    prelude: Output,
+    block: Option<Vec<Item>>,
+}
+
+/// Group rules contain rules that are nested in the surrounding rule, others,
+/// e.g., `@font-face`, contain declarations of their own.
+fn is_group(name: &str) -> bool {
+    matches!(
+        name,
+        "@media" | "@supports" | "@container" | "@layer" | "@document"
+    ) || name.ends_with("keyframes")
+}
+
+/// Writes flattened `items` with the resolved selectors of their parent rule.
+fn write_items(output: &mut Output, parents: &[String], items: Vec<Item>) {
+    let mut declarations = Vec::new();
+    let mut nested = Vec::new();
+    for item in items {
+        match item {
+            Item::Declaration(declaration) => declarations.push(declaration),
+            item => nested.push(item),
+        }
+    }
+    if !declarations.is_empty() {
+        output.new_line();
+        output.push_str(&parents.join(", "));
+        write_declarations(output, declarations);
+    }
+    for item in nested {
+        match item {
+            Item::Declaration(_) => unreachable!(),
+            Item::Rule(Rule { selectors, items }) => {
+                let selectors = if parents.is_empty() {
+                    selectors
+                } else {
+                    parents
+                        .iter()
+                        .flat_map(|parent| {
+                            selectors.iter().map(move |selector| {
+                                if selector.contains('&') {
+                                    selector.replace('&', parent)
+                                } else {
+                                    format!("{parent} {selector}")
+                                }
+                            })
+                        })
+                        .collect()
+                };
+                write_items(output, &selectors, items);
+            }
+            Item::AtRule(AtRule {
+                name,
+                prelude,
+                block,
+            }) => {
+                output.new_line();
+                output.push_str(&name);
+                if !prelude.format.is_empty() {
+                    output.push_str(" ");
+                    output.push_output(prelude);
+                }
+                match block {
+                    Some(items) if is_group(&name) => {
+                        output.push_str(" {");
+                        write_items(output, parents, items);
+                        output.push_str("\n}");
+                    }
+                    Some(items) => write_declarations(
+                        output,
+                        items.into_iter().filter_map(|item| match item {
+                            Item::Declaration(declaration) => Some(declaration),
+                            _ => None,
+                        }),
+                    ),
+                    None => output.push_str(";"),
+                }
+            }
         }
     }
-    Ok(output)
 }
 
-// Maybe I should reconsider... feels like I'm reimplementing all of scss :D
-// @rules
-// @charset "<charser>";
-// @color-profile <ident> {<parameters>}
-// @container <container-condition> {<stylesheet>}
-#[derive(derive_more::Display)]
-enum AtRule {
-    Charset(String)
+fn write_declarations(output: &mut Output, declarations: impl IntoIterator<Item = Declaration>) {
+    output.push_str(" {");
+    for Declaration { property, value } in declarations {
+        output.push_str(" ");
+        output.push_str(&property);
+        output.push_str(": ");
+        output.push_output(value);
+        output.push_str(";");
+    }
+    output.push_str(" }");
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(p) if p.as_char() == c)
+}
+
+fn is_brace(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace)
+}
+
+/// Parses the items of a block, `nested` blocks, i.e., inside a rule, can
+/// contain declarations.
+///
+/// In nested blocks, items starting with `property:` are declarations, so
+/// nested rules need to start with something else, e.g., `&`.
+fn items(tokens: &[TokenTree], nested: bool) -> Result<Vec<Item>> {
+    let mut parsed = Vec::new();
+    let mut tokens = tokens;
+    while let Some(first) = tokens.first() {
+        if is_punct(first, ';') {
+            tokens = &tokens[1..];
+        } else if is_punct(first, '@') {
+            let end = tokens
+                .iter()
+                .position(|t| is_punct(t, ';') || is_brace(t))
+                .unwrap_or(tokens.len());
+            let Some(TokenTree::Ident(ident)) = tokens.get(1) else {
+                bail!(first, "expected at-rule name");
+            };
+            let mut name = format!("@{ident}");
+            let mut prelude = &tokens[2..end];
+            while let [TokenTree::Punct(p), TokenTree::Ident(ident), rest @ ..] = prelude {
+                if p.as_char() != '-' {
+                    break;
+                }
+                name.push('-');
+                name.push_str(&ident.to_string());
+                prelude = rest;
+            }
+            let mut output = Output::default();
+            write_prelude(&mut output, prelude);
+            let block = match tokens.get(end) {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                    let tokens: Vec<_> = group.stream().into_iter().collect();
+                    // Groups keep the surrounding nesting.
+                    Some(items(&tokens, nested || !is_group(&name))?)
+                }
+                _ => None,
+            };
+            parsed.push(Item::AtRule(AtRule {
+                name,
+                prelude: output,
+                block,
+            }));
+            tokens = tokens.get(end + 1..).unwrap_or_default();
+        } else if nested && is_declaration(tokens) {
+            let end = tokens
+                .iter()
+                .position(|t| is_punct(t, ';'))
+                .unwrap_or(tokens.len());
+            parsed.push(Item::Declaration(declaration(&tokens[..end])?));
+            tokens = tokens.get(end + 1..).unwrap_or_default();
+        } else {
+            let Some(end) = tokens.iter().position(is_brace) else {
+                bail!(
+                    tokens.last(),
+                    "expected `{{ ... }}` after selector";
+                    help = "declarations are only allowed in rules, e.g., `.class {{ property: value; }}`"
+                );
+            };
+            let TokenTree::Group(block) = &tokens[end] else {
+                unreachable!()
+            };
+            let block: Vec<_> = block.stream().into_iter().collect();
+            parsed.push(Item::Rule(Rule {
+                selectors: selectors(&tokens[..end])?,
+                items: items(&block, true)?,
+            }));
+            tokens = &tokens[end + 1..];
+        }
+    }
+    Ok(parsed)
+}
+
+/// Whether `tokens` start with `property:`.
+fn is_declaration(tokens: &[TokenTree]) -> bool {
+    let name = tokens
+        .iter()
+        .take_while(|t| matches!(t, TokenTree::Ident(_)) || is_punct(t, '-'))
+        .count();
+    name > 0 && tokens.get(name).is_some_and(|t| is_punct(t, ':'))
+}
+
+fn declaration(tokens: &[TokenTree]) -> Result<Declaration> {
+    let colon = tokens
+        .iter()
+        .position(|t| is_punct(t, ':'))
+        .expect("declaration contains `:`");
+    let (property, value) = (&tokens[..colon], &tokens[colon + 1..]);
+    ensure!(!value.is_empty(), tokens[colon], "expected value");
+    let mut name = String::new();
+    for token in property {
+        match token {
+            TokenTree::Ident(ident) => name.push_str(&ident.to_string()),
+            TokenTree::Punct(punct) if punct.as_char() == '-' => name.push('-'),
+            token => bail!(token, "expected property name"),
+        }
+    }
+    let mut output = Output::default();
+    write_value(&mut output, value, false);
+    Ok(Declaration {
+        property: name,
+        value: output,
+    })
+}
+
+/// Writes the prelude of an at-rule, e.g., `screen and (max-width: 600px)`.
+fn write_prelude(output: &mut Output, tokens: &[TokenTree]) {
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && !is_punct(token, ',') {
+            output.push_str(" ");
+        }
+        match token {
+            // Media features, e.g., `(max-width: 600px)`.
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                output.push_str("(");
+                let tokens: Vec<_> = group.stream().into_iter().collect();
+                write_value(output, &tokens, false);
+                output.push_str(")");
+            }
+            token => write_value(output, std::slice::from_ref(token), false),
+        }
+    }
+}
+
+/// What was written last, deciding whether the next token needs a space.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Prev {
+    /// Nothing, or something that needs no space after it, e.g., `,` or `(`.
+    Start,
+    /// Identifier, e.g., `solid` or a tag name.
+    Word,
+    /// Class, id or pseudo class name.
+    Name,
+    /// Number, string, `%` or a closing delimiter.
+    Value,
+    /// Number or interpolated expression, directly followed by units.
+    Number,
+    /// Prefix that is directly followed by the next token, e.g., `#` or `-`.
+    Attach,
+    /// Combinator, e.g., `>`.
+    Combinator,
+    /// `&` or `*`, followed directly by a compound selector if
+    /// [`Spacing::Joint`].
+    Prefix(Spacing),
+}
+
+/// Units that are joined with a preceding number or interpolation, e.g.,
+/// `1 em` or `{size}px`, as `1em` is not a valid Rust literal.
+const UNITS: &[&str] = &[
+    "em", "ex", "ch", "rem", "vw", "vh", "vmin", "vmax", "px", "cm", "mm", "in", "pt", "pc", "fr",
+    "deg", "rad", "turn", "s", "ms",
+];
+
+/// Writes a property value, `-` between identifiers is kept as part of the
+/// identifier, inside math functions like `calc()` it is an operator.
+fn write_value(output: &mut Output, tokens: &[TokenTree], math: bool) {
+    let mut prev = Prev::Start;
+    let mut last_ident = None;
+    let mut tokens = tokens.iter().peekable();
+    while let Some(token) = tokens.next() {
+        let space = matches!(prev, Prev::Word | Prev::Value | Prev::Number);
+        match token {
+            TokenTree::Ident(ident)
+                if prev == Prev::Number && UNITS.contains(&&*ident.to_string()) =>
+            {
+                output.push_str(&ident.to_string());
+                prev = Prev::Value;
+            }
+            TokenTree::Ident(ident) => {
+                if space {
+                    output.push_str(" ");
+                }
+                output.push_str(&ident.to_string());
+                last_ident = Some(ident.to_string());
+                prev = Prev::Word;
+            }
+            TokenTree::Literal(literal) => {
+                if space {
+                    output.push_str(" ");
+                }
+                let literal = literal.to_string();
+                prev = if literal.starts_with(|c: char| c.is_ascii_digit()) {
+                    Prev::Number
+                } else {
+                    Prev::Value
+                };
+                output.push_str(&literal);
+            }
+            TokenTree::Punct(punct) => match punct.as_char() {
+                '-' if prev == Prev::Word
+                    && matches!(
+                        tokens.peek(),
+                        Some(TokenTree::Ident(_) | TokenTree::Literal(_))
+                    ) =>
+                {
+                    output.push_str("-");
+                    prev = Prev::Attach;
+                }
+                '-' | '+' | '*' | '/' if math && matches!(prev, Prev::Value | Prev::Number) => {
+                    output.push_str(&format!(" {} ", punct.as_char()));
+                    prev = Prev::Start;
+                }
+                '-' | '#' | '.' | '!' => {
+                    if space {
+                        output.push_str(" ");
+                    }
+                    output.push_str(&punct.to_string());
+                    prev = Prev::Attach;
+                }
+                '%' => {
+                    output.push_str("%");
+                    prev = Prev::Value;
+                }
+                ',' | ':' => {
+                    output.push_str(&format!("{} ", punct.as_char()));
+                    prev = Prev::Start;
+                }
+                c => {
+                    output.push_str(&format!(" {c} "));
+                    prev = Prev::Start;
+                }
+            },
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Brace => {
+                        if space {
+                            output.push_str(" ");
+                        }
+                        output.push_expr(group.stream());
+                        prev = Prev::Number;
+                        continue;
+                    }
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                // Function calls, e.g., `url(...)`, have no space before `(`.
+                if space && !(prev == Prev::Word && open == "(") {
+                    output.push_str(" ");
+                }
+                let math = math
+                    || prev == Prev::Word
+                        && matches!(
+                            last_ident.as_deref(),
+                            Some("calc" | "min" | "max" | "clamp")
+                        );
+                output.push_str(open);
+                let tokens: Vec<_> = group.stream().into_iter().collect();
+                write_value(output, &tokens, math);
+                output.push_str(close);
+                prev = Prev::Value;
+            }
+        }
+    }
+}
+
+/// Converts a comma separated list of selectors.
+fn selectors(tokens: &[TokenTree]) -> Result<Vec<String>> {
+    tokens
+        .split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ','))
+        .map(|selector| {
+            ensure!(!selector.is_empty(), tokens.first(), "expected selector");
+            let mut output = String::new();
+            write_selector(&mut output, selector)?;
+            Ok(output.trim().to_owned())
+        })
+        .collect()
+}
+
+/// Writes a selector, classes, ids, pseudo classes and attributes directly
+/// following a tag name are part of the same compound selector, e.g.,
+/// `a.active`, following a class or id they are descendants, e.g.,
+/// `.card .title`.
+///
+/// Compound selectors starting with a class can be written using `&` in nested
+/// rules, e.g., `.btn { &.primary { ... } }`, or as a string literal, which is
+/// inserted verbatim.
+fn write_selector(output: &mut String, tokens: &[TokenTree]) -> Result<()> {
+    let mut prev = Prev::Start;
+    let mut tokens = tokens.iter().peekable();
+    while let Some(token) = tokens.next() {
+        // Whether the token starts a new compound selector.
+        let descendant = matches!(
+            prev,
+            Prev::Word | Prev::Name | Prev::Value | Prev::Prefix(Spacing::Alone)
+        );
+        match token {
+            TokenTree::Ident(ident) if prev == Prev::Attach => {
+                output.push_str(&ident.to_string());
+                prev = Prev::Name;
+            }
+            TokenTree::Ident(ident) => {
+                if descendant {
+                    output.push(' ');
+                }
+                output.push_str(&ident.to_string());
+                prev = Prev::Word;
+            }
+            TokenTree::Literal(literal) => {
+                if descendant {
+                    output.push(' ');
+                }
+                match syn::Lit::new(literal.clone()) {
+                    syn::Lit::Str(verbatim) => output.push_str(&verbatim.value()),
+                    _ => output.push_str(&literal.to_string()),
+                }
+                prev = Prev::Value;
+            }
+            TokenTree::Punct(punct) => match punct.as_char() {
+                // Hyphenated identifier, e.g., `.btn-primary`.
+                '-' if matches!(prev, Prev::Word | Prev::Name)
+                    && matches!(tokens.peek(), Some(TokenTree::Ident(_))) =>
+                {
+                    output.push('-');
+                    output.extend(tokens.next().map(ToString::to_string));
+                }
+                '-' => {
+                    output.push('-');
+                    prev = Prev::Attach;
+                }
+                '.' | '#' => {
+                    if descendant && prev != Prev::Word {
+                        output.push(' ');
+                    }
+                    output.push(punct.as_char());
+                    prev = Prev::Attach;
+                }
+                ':' => {
+                    if prev == Prev::Prefix(Spacing::Alone) {
+                        output.push(' ');
+                    }
+                    output.push(':');
+                    prev = Prev::Attach;
+                }
+                '>' | '+' | '~' => {
+                    output.push_str(&format!(" {} ", punct.as_char()));
+                    prev = Prev::Combinator;
+                }
+                // Keyframe selectors, e.g., `50%`.
+                '%' => output.push('%'),
+                '&' | '*' => {
+                    if descendant {
+                        output.push(' ');
+                    }
+                    output.push(punct.as_char());
+                    prev = Prev::Prefix(punct.spacing());
+                }
+                _ => bail!(punct, "unexpected `{punct}` in selector"),
+            },
+            TokenTree::Group(group) => match group.delimiter() {
+                Delimiter::Bracket => {
+                    if prev == Prev::Prefix(Spacing::Alone) {
+                        output.push(' ');
+                    }
+                    // Attribute selectors contain no whitespace, except in
+                    // strings.
+                    output.push('[');
+                    output.extend(group.stream().into_iter().map(|t| t.to_string()));
+                    output.push(']');
+                    prev = Prev::Value;
+                }
+                // Functional pseudo classes, e.g., `:not(...)`.
+                Delimiter::Parenthesis if prev == Prev::Name => {
+                    output.push('(');
+                    let tokens: Vec<_> = group.stream().into_iter().collect();
+                    write_selector(output, &tokens)?;
+                    output.push(')');
+                    prev = Prev::Value;
+                }
+                _ => bail!(group, "unexpected group in selector"),
+            },
+        }
+    }
+    Ok(())
 }
@@ -1,18 +1,21 @@
+use std::iter::Peekable;
+
 use htmx_script::{Script, ToJs};
 use manyhow::{ensure, Error, ErrorMessage, Result};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use proc_macro2::{token_stream, Delimiter, Group, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, ToTokens};
 use rstml::atoms::{CloseTag, OpenTag};
 use rstml::node::{
-    AttributeValueExpr, KeyedAttribute, KeyedAttributeValue, NodeAttribute, NodeBlock, NodeElement,
-    NodeFragment, NodeName,
+    AttributeValueExpr, KeyedAttribute, KeyedAttributeValue, NodeAttribute, NodeBlock, NodeComment,
+    NodeElement, NodeFragment, NodeName,
 };
 use rstml::recoverable::Recoverable;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{parse2, Expr, ExprLit, ExprPath, Lit, LitStr, Stmt};
 
 use super::special_components::{Node, Special};
-use super::try_into_iter;
+use super::{try_into_iter, try_into_nodes};
 use crate::*;
 
 pub fn html(input: TokenStream) -> Result {
@@ -24,26 +27,208 @@ pub fn html(input: TokenStream) -> Result {
             .raw_text_elements(["script"].into()),
     )
     // TODO parse_recoverable
-    .parse_simple(input)?;
+    .parse_simple(shorthands(input))?;
 
     super::expand_nodes(nodes)
 }
 
+/// Desugars attribute syntax rstml does not support inside open tags:
+/// - `@event` attribute names are translated to `onevent`.
+/// - `on:event={js}` is translated to `onevent={::htmx::js!{js}}`, `on:event`
+///   without a braced value to `onevent`. Only an `on` starting the attribute
+///   name is translated, so htmx's `hx-on:event` is kept.
+/// - `attr=?value` is translated to
+///   `attr={::htmx::__private::conditional(value)}`.
+fn shorthands(input: TokenStream) -> TokenStream {
+    let mut output = TokenStream::new();
+    // Nesting of `<` `>`, this also covers generics in attribute values.
+    let mut depth = 0usize;
+    let mut input = input.into_iter().peekable();
+    while let Some(token) = input.next() {
+        match token {
+            // Rust code in control flow, e.g., `if a < b {`, cannot contain tags,
+            // it is copied until the block.
+            TokenTree::Ident(keyword)
+                if depth == 0
+                    && matches!(
+                        keyword.to_string().as_str(),
+                        "if" | "for" | "while" | "match" | "let"
+                    ) =>
+            {
+                output.extend([TokenTree::Ident(keyword)]);
+                while let Some(token) = input.next_if(
+                    |t| !matches!(t, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace),
+                ) {
+                    output.extend([token]);
+                }
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == '<'
+                    && (depth > 0
+                        || matches!(
+                            input.peek(),
+                            Some(TokenTree::Ident(_) | TokenTree::Group(_))
+                        )) =>
+            {
+                depth += 1;
+                output.extend([TokenTree::Punct(punct)]);
+            }
+            TokenTree::Punct(punct) if depth > 0 && punct.as_char() == '>' => {
+                depth -= 1;
+                output.extend([TokenTree::Punct(punct)]);
+            }
+            TokenTree::Punct(punct)
+                if depth > 0
+                    && punct.as_char() == '@'
+                    && matches!(input.peek(), Some(TokenTree::Ident(_))) =>
+            {
+                let Some(TokenTree::Ident(event)) = input.next() else {
+                    unreachable!("peeked ident")
+                };
+                output.extend([TokenTree::Ident(format_ident!(
+                    "on{}",
+                    event.unraw(),
+                    span = event.span()
+                ))]);
+            }
+            TokenTree::Ident(on)
+                if depth > 0
+                    && on == "on"
+                    && !continues_name(&output)
+                    && is_event_shorthand(&input) =>
+            {
+                input.next().expect("peeked `:`");
+                let Some(TokenTree::Ident(event)) = input.next() else {
+                    unreachable!("peeked ident")
+                };
+                output.extend([TokenTree::Ident(format_ident!(
+                    "on{}",
+                    event.unraw(),
+                    span = event.span()
+                ))]);
+                if matches!(input.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=') {
+                    output.extend(input.next());
+                    if let Some(TokenTree::Group(script)) = input.next_if(
+                        |t| matches!(t, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace),
+                    ) {
+                        let stream = script.stream();
+                        let mut block = Group::new(
+                            Delimiter::Brace,
+                            quote_spanned!(script.span()=> ::htmx::js!{#stream}),
+                        );
+                        block.set_span(script.span());
+                        output.extend([TokenTree::Group(block)]);
+                    }
+                }
+            }
+            TokenTree::Punct(punct)
+                if depth > 0
+                    && punct.as_char() == '='
+                    && matches!(input.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '?') =>
+            {
+                let question = input.next().expect("peeked `?`");
+                let value = conditional_value(&mut input);
+                output.extend([TokenTree::Punct(punct)]);
+                if value.is_empty() {
+                    output.extend([question]);
+                } else {
+                    let mut block = Group::new(
+                        Delimiter::Brace,
+                        quote_spanned!(question.span()=> ::htmx::__private::conditional(#value)),
+                    );
+                    block.set_span(question.span());
+                    output.extend([TokenTree::Group(block)]);
+                }
+            }
+            // Blocks start a new context, e.g., the body of an `if`.
+            TokenTree::Group(group) if depth == 0 || group.delimiter() == Delimiter::Brace => {
+                let mut new = Group::new(group.delimiter(), shorthands(group.stream()));
+                new.set_span(group.span());
+                output.extend([TokenTree::Group(new)]);
+            }
+            token => output.extend([token]),
+        }
+    }
+    output
+}
+
+/// Whether the last token of `output` is `-` or `:`, i.e., the following
+/// ident is part of a longer attribute name, e.g., `on` in `hx-on:click`.
+fn continues_name(output: &TokenStream) -> bool {
+    matches!(
+        output.clone().into_iter().last(),
+        Some(TokenTree::Punct(p)) if matches!(p.as_char(), '-' | ':')
+    )
+}
+
+/// Whether the tokens following `on` are `:event`.
+fn is_event_shorthand(input: &Peekable<token_stream::IntoIter>) -> bool {
+    let mut ahead = input.clone();
+    matches!(
+        ahead.next(),
+        Some(TokenTree::Punct(colon)) if colon.as_char() == ':' && colon.spacing() == Spacing::Alone
+    ) && matches!(ahead.next(), Some(TokenTree::Ident(_)))
+}
+
+/// Takes the value following `attr=?`, i.e., a path, field access or call
+/// chain like `self.value()` or a block.
+fn conditional_value(input: &mut Peekable<token_stream::IntoIter>) -> TokenStream {
+    let mut value = TokenStream::new();
+    loop {
+        match input.peek() {
+            Some(TokenTree::Ident(_) | TokenTree::Literal(_) | TokenTree::Group(_)) => {
+                value.extend(input.next());
+            }
+            _ => return value,
+        }
+        // Calls and indexing.
+        while matches!(input.peek(), Some(TokenTree::Group(g)) if g.delimiter() != Delimiter::Brace)
+        {
+            value.extend(input.next());
+        }
+        match input.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '.' => value.extend(input.next()),
+            Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint => {
+                value.extend(input.next());
+                value.extend(input.next());
+            }
+            _ => return value,
+        }
+    }
+}
+
 impl TryFrom<Node> for super::Node {
     type Error = Error;
 
     fn try_from(value: Node) -> std::result::Result<Self, Self::Error> {
         match value {
-            Node::Comment(comment) => bail!(comment, "html comments are not supported"),
+            Node::Comment(NodeComment { value, .. }) => {
+                Ok(super::Node::Block(quote!(::htmx::Comment::new(#value))))
+            }
             Node::Doctype(doc_type) => bail!(doc_type, "doc typ is set automatically"),
             Node::Fragment(NodeFragment { tag_open, .. }) => bail!(tag_open, "missing tag name"),
             Node::Element(element) => Ok(super::Node::Element(element.try_into()?)),
             Node::Block(block) => Ok(super::Node::Block(block.into_token_stream())),
             Node::Text(text) => Ok(super::Node::String(text.value)),
-            Node::RawText(text) => bail!(
-                text.into_token_stream().into_iter().next(),
-                "expected `<`, `{{` or `\"`"
-            ),
+            // Numeric and boolean literals are rendered as text.
+            Node::RawText(text) => match parse2::<Lit>(text.to_token_stream()) {
+                Ok(Lit::Int(lit)) => Ok(super::Node::String(LitStr::new(
+                    lit.base10_digits(),
+                    lit.span(),
+                ))),
+                Ok(Lit::Float(lit)) => Ok(super::Node::String(LitStr::new(
+                    lit.base10_digits(),
+                    lit.span(),
+                ))),
+                Ok(Lit::Bool(lit)) => Ok(super::Node::String(LitStr::new(
+                    &lit.value.to_string(),
+                    lit.span,
+                ))),
+                _ => bail!(
+                    text.into_token_stream().into_iter().next(),
+                    "expected `<`, `{{` or `\"`"
+                ),
+            },
             Node::Custom(special) => special.try_into(),
         }
     }
@@ -83,8 +268,14 @@ impl TryFrom<NodeElement<Special>> for super::Element {
                     // quote!(__html.body(#script);)
                     super::ElementBody::Script(super::ScriptBody::Expr(script.into_token_stream()))
                 }
+            } else if let (true, [Node::Block(block)]) = (
+                matches!(&open_tag.name, NodeName::Path(p) if p.path.is_ident("style")),
+                &children[..],
+            ) {
+                // A block as `<style>` body is CSS, i.e., `ToStyle`.
+                super::ElementBody::Script(super::ScriptBody::Expr(block.into_token_stream()))
             } else {
-                super::ElementBody::Children(try_into_iter(children)?)
+                super::ElementBody::Children(try_into_nodes(children)?)
             },
             open_tag: open_tag.name.try_into()?,
         })
@@ -140,10 +331,21 @@ impl TryFrom<NodeAttribute> for super::Attribute {
                 },
                 value: None,
             },
-            NodeAttribute::Attribute(attribute) => super::Attribute {
-                value: attribute.value().map(ToTokens::into_token_stream),
-                key: attribute.key.try_into()?,
-            },
+            NodeAttribute::Attribute(attribute) => {
+                // A dangling `=`, e.g., `class= id="a"`, is parsed as the assignment `id="a"`.
+                if let (Some(Expr::Assign(_)), Some(AttributeValueExpr { token_eq, .. })) =
+                    (attribute.value(), attribute.possible_value.to_value())
+                {
+                    bail!(
+                        token_eq, "missing value for attribute `{}`", attribute.key;
+                        help = "to use an assignment as value, wrap it in braces `{{a = b}}`"
+                    );
+                }
+                super::Attribute {
+                    value: attribute.value().map(ToTokens::into_token_stream),
+                    key: attribute.key.try_into()?,
+                }
+            }
         })
     }
 }
@@ -154,18 +356,16 @@ impl TryFrom<NodeName> for super::AttributeKey {
     fn try_from(value: NodeName) -> std::result::Result<Self, Self::Error> {
         Ok(match value {
             NodeName::Path(p) if p.path.get_ident().is_some() => {
-                super::AttributeKey::Fn(p.into_token_stream())
+                super::AttributeKey::from_path(&p.path)?
             }
-            NodeName::Path(p) if p.path.segments.first().is_some_and(|hx| hx.ident == "hx") => {
-                let sident = p
-                    .path
+            NodeName::Path(p)
+                if p.path
                     .segments
-                    .iter()
-                    .map(|i| i.ident.to_string().replace('_', "-"))
-                    // hx::swap::oob
-                    .collect::<Vec<_>>()
-                    .join("-");
-                super::AttributeKey::from_str(sident, p.span())?
+                    .first()
+                    .is_some_and(|prefix| prefix.ident == "hx" || prefix.ident == "data") =>
+            {
+                // hx::swap::oob
+                super::AttributeKey::from_path(&p.path)?
             }
             key @ (NodeName::Punctuated(_) | NodeName::Path(_)) => {
                 super::AttributeKey::from_str(key.to_string(), key.span())?
@@ -338,11 +538,20 @@ fn name_to_struct(name: NodeName) -> Result<(TokenStream, NodeType)> {
 
 fn attribute_key_to_fn(name: NodeName, value: impl ToTokens, custom: bool) -> Result {
     Ok(match name {
-        NodeName::Path(ExprPath { path, .. }) if !custom && path.get_ident().is_some() => {
+        NodeName::Path(ExprPath { path, .. })
+            if !custom
+                && path
+                    .get_ident()
+                    .is_some_and(|ident| !ident.to_string().starts_with("data_")) =>
+        {
             quote!(#path(#value))
         }
         NodeName::Path(ExprPath { path, .. })
-            if path.segments.first().is_some_and(|hx| hx.ident == "hx") =>
+            if path.segments.first().is_some_and(|prefix| {
+                prefix.ident == "hx"
+                    || prefix.ident == "data"
+                    || prefix.ident.to_string().starts_with("data_")
+            }) =>
         {
             {
                 let sident = path
@@ -363,3 +572,30 @@ fn attribute_key_to_fn(name: NodeName, value: impl ToTokens, custom: bool) -> Re
         name @ NodeName::Block(_) => quote_spanned!(name.span()=>  custom_attr(#name, #value)),
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[track_caller]
+    fn assert_error(input: TokenStream, message: &str) {
+        let error = format!("{:?}", html(input).expect_err("should fail to parse"));
+        assert!(
+            error.contains(message),
+            "`{error}` should contain `{message}`"
+        );
+    }
+
+    #[test]
+    fn malformed_attributes() {
+        assert_error(quote!(<div class= />), "missing attribute value");
+        assert_error(quote!(<div class=>), "missing attribute value");
+        assert_error(
+            quote!(<div class= id="a"/>),
+            "missing value for attribute `class`",
+        );
+        assert_error(quote!(<div = "a"/>), "invalid tag name or attribute key");
+        assert_error(quote!(<div class="a" =/>), "expected an expression");
+        assert_error(quote!(<a href=?/>), "expected an expression");
+    }
+}
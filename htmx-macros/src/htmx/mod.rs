@@ -4,11 +4,15 @@ mod special_components;
 
 pub mod rusty;
 
+use std::mem;
+
 use html_escape::{encode_safe, encode_script};
-use manyhow::ensure;
+use manyhow::{ensure, ErrorMessage};
 use proc_macro2::{Literal, Span};
+use quote::format_ident;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
-use syn::LitStr;
+use syn::{parse2, Ident, LitStr};
 
 use super::*;
 
@@ -33,24 +37,59 @@ use super::*;
 //     }
 // }
 
+/// Checks for characters valid in [custom element names](https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname).
+pub(crate) fn is_valid_tag_name(name: &str) -> bool {
+    name.to_ascii_lowercase().chars()
+        .all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}'))
+}
+
+pub(crate) fn is_keyword(ident: &Ident) -> bool {
+    match ident.unraw().to_string().as_str() {
+        // Based on https://doc.rust-lang.org/1.65.0/reference/keywords.html
+        "abstract" | "as" | "async" | "await" | "become" | "box" | "break" | "const"
+        | "continue" | "crate" | "do" | "dyn" | "else" | "enum" | "extern" | "false" | "final"
+        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod"
+        | "move" | "mut" | "override" | "priv" | "pub" | "ref" | "return" | "Self" | "self"
+        | "static" | "struct" | "super" | "trait" | "true" | "try" | "type" | "typeof"
+        | "unsafe" | "unsized" | "use" | "virtual" | "where" | "while" | "yield" => true,
+        _ => false,
+    }
+}
+
 fn try_into_iter<T>(
     input: impl IntoIterator<Item = impl TryInto<T, Error = manyhow::Error>>,
 ) -> Result<Vec<T>> {
     input.into_iter().map(TryInto::try_into).collect()
 }
 
+/// Converts a list of sibling nodes, a `let` takes the nodes following it
+/// as its body, see [`Let`].
+fn try_into_nodes(
+    input: impl IntoIterator<Item = impl TryInto<Node, Error = manyhow::Error>>,
+) -> Result<Vec<Node>> {
+    fn nest_lets(mut nodes: Vec<Node>) -> Vec<Node> {
+        if let Some(index) = nodes.iter().position(|node| matches!(node, Node::Let(_))) {
+            let body = nest_lets(nodes.split_off(index + 1));
+            if let Some(Node::Let(let_)) = nodes.last_mut() {
+                let_.body = body;
+            }
+        }
+        nodes
+    }
+    try_into_iter(input).map(nest_lets)
+}
+
 fn expand_nodes(
     nodes: impl IntoIterator<Item = impl TryInto<Node, Error = manyhow::Error>>,
 ) -> Result {
-    let nodes = nodes
-        .into_iter()
-        .map(TryInto::try_into)
-        .collect::<Result<Vec<Node>>>()?;
+    let nodes = try_into_nodes(nodes)?;
+    check_jumps(&nodes, false)?;
     Ok(quote! {
         ::htmx::Fragment(move |mut __html: &mut ::htmx::Html| {
             #[allow(unused_braces)]
             {
                 use ::htmx::native::*;
+                use ::htmx::svg::*;
                 use ::htmx::IntoHtml as _;
                 #(#nodes)*
             };
@@ -58,12 +97,60 @@ fn expand_nodes(
     })
 }
 
+/// Ensures `break` and `continue` are only used in the body of a loop, the
+/// body of an element is a closure, so they cannot reach a loop around it.
+fn check_jumps(nodes: &[Node], in_loop: bool) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Jump(jump) => ensure!(
+                in_loop,
+                jump,
+                "`break` and `continue` are only allowed in the body of a `for`, `while` or \
+                 `loop`, not inside an element in it"
+            ),
+            Node::If(if_) => check_if_jumps(if_, in_loop)?,
+            Node::For(For { body, .. })
+            | Node::While(While { body, .. })
+            | Node::Loop(Loop { body }) => {
+                check_jumps(body, true)?;
+            }
+            Node::Let(Let {
+                else_branch, body, ..
+            }) => {
+                check_jumps(else_branch, in_loop)?;
+                check_jumps(body, in_loop)?;
+            }
+            Node::Element(Element {
+                body: ElementBody::Children(children),
+                ..
+            }) => {
+                check_jumps(children, false)?;
+            }
+            Node::String(_) | Node::Block(_) | Node::FunctionCall(_) | Node::Element(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_if_jumps(if_: &If, in_loop: bool) -> Result<()> {
+    check_jumps(&if_.then_branch, in_loop)?;
+    match &if_.else_branch {
+        ElseBranch::None => Ok(()),
+        ElseBranch::Else(nodes) => check_jumps(nodes, in_loop),
+        ElseBranch::ElseIf(if_) => check_if_jumps(if_, in_loop),
+    }
+}
+
 enum Node {
     String(LitStr),
     Block(TokenStream),
     If(If),
     For(For),
     While(While),
+    Loop(Loop),
+    /// `break` or `continue`.
+    Jump(TokenStream),
+    Let(Let),
     FunctionCall(FunctionCall),
     Element(Element),
 }
@@ -84,6 +171,9 @@ impl ToTokens for Node {
             Node::If(if_) => if_.to_tokens(tokens),
             Node::For(for_) => for_.to_tokens(tokens),
             Node::While(while_) => while_.to_tokens(tokens),
+            Node::Loop(loop_) => loop_.to_tokens(tokens),
+            Node::Jump(jump) => jump.to_tokens(tokens),
+            Node::Let(let_) => let_.to_tokens(tokens),
             Node::FunctionCall(call) => call.to_tokens(tokens),
             Node::Element(element) => element.to_tokens(tokens)
         }
@@ -163,6 +253,51 @@ impl ToTokens for While {
     }
 }
 
+struct Loop {
+    body: Vec<Node>,
+}
+
+impl ToTokens for Loop {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { body } = self;
+        quote! {
+            loop {
+                #(#body)*
+            }
+        }
+        .to_tokens(tokens)
+    }
+}
+
+/// `let PAT = EXPR else { ... };`, rendering the nodes following it in the
+/// same body when `PAT` matches and the else branch otherwise.
+struct Let {
+    pat: TokenStream,
+    expr: TokenStream,
+    else_branch: Vec<Node>,
+    /// The following sibling nodes, set by [`try_into_nodes`].
+    body: Vec<Node>,
+}
+
+impl ToTokens for Let {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            pat,
+            expr,
+            else_branch,
+            body,
+        } = self;
+        quote! {
+            if let #pat = #expr {
+                #(#body)*
+            } else {
+                #(#else_branch)*
+            }
+        }
+        .to_tokens(tokens)
+    }
+}
+
 struct FunctionCall {
     function: TokenStream,
     args: Vec<TokenStream>,
@@ -196,17 +331,53 @@ impl ToTokens for Element {
 
         let mut attributes = attributes.clone();
 
+        // `ref=name` binds a generated id to `name`, and sets it as the element's
+        // `id`, making it available to following nodes, e.g., as `$name` in
+        // scripts.
+        let mut refs = Vec::new();
+        for attribute in &mut attributes {
+            if let (AttributeKey::Fn(key), Some(value)) = (&attribute.key, &attribute.value) {
+                if key == "ref" {
+                    if let Ok(name) = parse2::<Ident>(value.clone()) {
+                        let prefix = name.unraw().to_string();
+                        refs.push(quote!(let #name = ::htmx::__private::ref_id(__html, #prefix);));
+                        attribute.key = AttributeKey::Fn(Ident::new("id", key.span()));
+                        attribute.value = Some(quote!(&#name));
+                    }
+                }
+            }
+        }
+
         if !matches!(open_tag, OpenTag::Path(_)) {
             for attribute in &mut attributes {
-                if let AttributeKey::Fn(name) = &attribute.key {
-                    attribute.key = AttributeKey::from_str(name.to_string(), name.span())
-                        .expect("idents should be valid attribute keys")
-                }
+                let key = mem::replace(&mut attribute.key, AttributeKey::Expr(TokenStream::new()));
+                attribute.key = key.into_custom();
             }
         };
 
+        // Native elements merge all `class` attributes into one, they are added
+        // where the first `class` is and closed before the remaining attributes.
+        // Components are handled the same, for them `close_attr` is a no-op.
+        let classes: Vec<_> = attributes
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| matches!(&a.key, AttributeKey::Fn(key) if key == "class"))
+            .map(|(idx, _)| idx)
+            .collect();
+        let mut attributes: Vec<_> = attributes.iter().map(ToTokens::to_token_stream).collect();
+        if let (OpenTag::Path(_), Some(&first)) = (open_tag, classes.first()) {
+            let mut merged = TokenStream::new();
+            for &idx in &classes {
+                merged.extend(mem::take(&mut attributes[idx]));
+            }
+            merged.extend(quote!(.close_attr()));
+            attributes[first] = merged;
+        }
+
         quote! {
+            #(#refs)*
             {{
+                use ::htmx::__private::{CloseAttr as _, ForwardAttrs as _};
                 #( use ::htmx::__private::Unused; #close_tag::unused(); )*
                 #open_tag #(#attributes)* #body
             }.into_html(&mut __html)}
@@ -224,8 +395,7 @@ enum OpenTag {
 impl OpenTag {
     fn from_str(name: String, span: Span) -> Result<OpenTag> {
         ensure!(
-            name.to_ascii_lowercase().chars()
-                .all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')),
+            is_valid_tag_name(&name),
             span,
             "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
             // TODO similar function but with css error: https://drafts.csswg.org/css-syntax-3/#non-ascii-ident-code-point
@@ -242,7 +412,7 @@ impl ToTokens for OpenTag {
                 let name = quote_spanned!(*span=> #name);
                 quote!(::htmx::CustomElement::new_unchecked(&mut __html, #name))
             }
-            OpenTag::Expr(name) => quote!(quote!(::htmx::CustomElement::new(&mut __html, #name)),),
+            OpenTag::Expr(name) => quote!(::htmx::CustomElement::new(&mut __html, #name)),
         }
         .to_tokens(tokens)
     }
@@ -260,6 +430,11 @@ impl ToTokens for Attribute {
         let Self { key, value } = self;
         let value = value.clone().unwrap_or_else(|| quote!(true));
         match key {
+            // Keywords, e.g., `type` or `for` are exposed as `type_` and `for_`.
+            AttributeKey::Fn(fun) if is_keyword(fun) => {
+                let fun = format_ident!("{}_", fun.unraw(), span = fun.span());
+                quote!(.#fun(#value))
+            }
             AttributeKey::Fn(fun) => quote!(.#fun(#value)),
             AttributeKey::String(key, span) => {
                 let key = quote_spanned!(*span => #key);
@@ -271,15 +446,76 @@ impl ToTokens for Attribute {
     }
 }
 
-#[derive(Clone)]
+/// htmx attributes with typed setters, e.g., `hx_swap`.
+const TYPED_HTMX_ATTRIBUTES: &[&str] = &["swap", "target"];
+
+#[derive(Clone, Debug)]
 enum AttributeKey {
-    Fn(TokenStream),
+    Fn(Ident),
     String(String, Span),
     Expr(TokenStream),
 }
 
 impl AttributeKey {
-    fn from_str(value: String, span: Span) -> Result<AttributeKey> {
+    /// Creates a key from a path, `hx::` and `data::` paths, as well as names
+    /// starting with `data_`, are translated to `-` separated keys, e.g.,
+    /// `hx::swap_oob` to `hx-swap-oob`.
+    fn from_path(path: &syn::Path) -> Result<AttributeKey, ErrorMessage> {
+        let first = &path.segments.first().expect("paths are not empty").ident;
+        // `hx::swap` and `hx::target` have typed setters.
+        if let [_, second] = &path.segments.iter().collect::<Vec<_>>()[..] {
+            if first == "hx"
+                && TYPED_HTMX_ATTRIBUTES
+                    .iter()
+                    .any(|name| second.ident == name)
+            {
+                return Ok(AttributeKey::Fn(format_ident!(
+                    "hx_{}",
+                    second.ident,
+                    span = path.span()
+                )));
+            }
+        }
+        // A bare `data` is a normal attribute, e.g., on `<object>`.
+        if first == "hx"
+            || first == "data" && path.segments.len() > 1
+            || path.segments.len() == 1 && first.to_string().starts_with("data_")
+        {
+            let key = path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string().replace('_', "-"))
+                .collect::<Vec<_>>()
+                .join("-");
+            Self::from_str(key, path.span())
+        } else if let Some(ident) = path.get_ident() {
+            Ok(AttributeKey::Fn(ident.clone()))
+        } else {
+            Self::from_str(
+                path.to_token_stream().to_string().replace(' ', ""),
+                path.span(),
+            )
+        }
+    }
+
+    /// Custom elements only support [`AttributeKey::String`] and
+    /// [`AttributeKey::Expr`].
+    fn into_custom(self) -> AttributeKey {
+        match self {
+            AttributeKey::Fn(name)
+                if TYPED_HTMX_ATTRIBUTES
+                    .iter()
+                    .any(|typed| name.to_string().strip_prefix("hx_") == Some(typed)) =>
+            {
+                AttributeKey::Fn(name)
+            }
+            AttributeKey::Fn(name) => AttributeKey::from_str(name.unraw().to_string(), name.span())
+                .expect("idents should be valid attribute keys"),
+            key => key,
+        }
+    }
+
+    fn from_str(value: String, span: Span) -> Result<AttributeKey, ErrorMessage> {
         ensure!(
             !value.to_string().chars().any(|c| c.is_whitespace()
                 || c.is_control()
@@ -287,6 +523,17 @@ impl AttributeKey {
             span,
             "invalid key `{value}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0"
         );
+        // XML namespaced attributes, e.g., `xlink:href`, need to be qualified names.
+        if let Some((prefix, local)) = value.split_once(':') {
+            if matches!(prefix, "xml" | "xmlns" | "xlink") {
+                ensure!(
+                    !local.is_empty() && !local.contains(':'),
+                    span,
+                    "invalid qualified name `{value}`, expected `{prefix}:name`";
+                    note = "https://www.w3.org/TR/xml-names/#ns-qualnames"
+                );
+            }
+        }
         Ok(AttributeKey::String(value, span))
     }
 }
@@ -324,7 +571,7 @@ impl ToTokens for ScriptBody {
                 let value = encode_script(&value);
                 let mut value = Literal::string(&value);
                 value.set_span(lit.span());
-                quote!(RawSrc(#value)).to_tokens(tokens)
+                quote!(::htmx::RawSrc::new(#value)).to_tokens(tokens)
             }
             ScriptBody::Expr(expr) => expr.to_tokens(tokens),
         }
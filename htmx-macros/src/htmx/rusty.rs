@@ -8,11 +8,13 @@ use syn::ext::IdentExt;
 use syn::parse::discouraged::Speculative;
 use syn::parse::{Parse, ParseStream, Parser, Peek};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::token::{Brace, Bracket, Paren};
 use syn::{bracketed, parenthesized, parse2, BinOp, Expr, LitStr, Pat, Path, Token};
 use syn_derive::{Parse, ToTokens};
 
 use super::html::ensure_tag_name;
+use super::{is_keyword, Attribute, AttributeKey};
 use crate::*;
 
 pub fn rtml(input: TokenStream) -> Result<proc_macro2::TokenStream, manyhow::Error> {
@@ -21,6 +23,7 @@ pub fn rtml(input: TokenStream) -> Result<proc_macro2::TokenStream, manyhow::Err
     Ok(quote! {
         ::htmx::Fragment(|mut __html: &mut ::htmx::Html| {
             use ::htmx::native::*;
+            use ::htmx::svg::*;
             #(#nodes)*
         })
     })
@@ -51,6 +54,10 @@ enum Node {
     #[parse(peek = Token![while])]
     While(While),
     // TODO controlflow
+    // `Func(args)` would be ambiguous with a component, so this uses the same
+    // syntax as `html!`.
+    #[parse(peek = Token![<])]
+    FunctionCall(FunctionCall),
     Element(Element),
 }
 
@@ -67,10 +74,36 @@ impl Node {
             Node::If(node) => node.expand(),
             Node::For(node) => node.expand(),
             Node::While(node) => node.expand(),
+            Node::FunctionCall(node) => node.expand(),
         }
     }
 }
 
+#[derive(Debug, Parse)]
+struct FunctionCall {
+    #[allow(unused)]
+    open_token: Token![<],
+    function: syn::ExprPath,
+    #[syn(parenthesized)]
+    #[allow(unused)]
+    paren: Paren,
+    #[syn(in = paren)]
+    #[parse(Punctuated::parse_terminated)]
+    args: Punctuated<Expr, Token![,]>,
+    #[allow(unused)]
+    slash: Token![/],
+    #[allow(unused)]
+    gt_token: Token![>],
+}
+
+impl FunctionCall {
+    fn expand(self) -> TokenStream {
+        let Self { function, args, .. } = self;
+        let args = args.into_iter();
+        quote!(::htmx::IntoHtml::into_html(#function(#(Into::into(#args),)*), &mut __html);)
+    }
+}
+
 #[derive(Debug, Parse, ToTokens)]
 struct Block {
     #[syn(braced)]
@@ -385,7 +418,20 @@ struct Element {
 impl Element {
     fn expand(self) -> TokenStream {
         let mut attrs = self.attrs.unwrap_or_default();
+        if matches!(self.path, ElementName::String(_) | ElementName::Block(_)) {
+            attrs.attrs = mem::take(&mut attrs.attrs)
+                .into_iter()
+                .map(Attr::into_custom)
+                .collect();
+        }
         let mut close_arg = quote!();
+        let native = match &self.path {
+            ElementName::Classes(_) => true,
+            ElementName::Path(path) => path
+                .get_ident()
+                .is_some_and(|i| !i.to_string().contains(char::is_uppercase)),
+            _ => false,
+        };
         let name = match self.path {
             ElementName::String(name) => {
                 quote!(::htmx::CustomElement::new_unchecked(&mut __html, #name);)
@@ -417,7 +463,28 @@ impl Element {
         )
         .peekable();
 
-        let attrs = attrs.attrs.into_iter().map(Attr::expand);
+        // Native elements merge all classes into a single `class` attribute, added
+        // where the first class is.
+        let mut classes = None;
+        let mut expanded = Vec::new();
+        for attr in attrs.attrs {
+            if native && attr.is_class() {
+                classes
+                    .get_or_insert_with(|| {
+                        expanded.push(TokenStream::new());
+                        (expanded.len() - 1, TokenStream::new())
+                    })
+                    .1
+                    .extend(attr.expand());
+            } else {
+                expanded.push(attr.expand());
+            }
+        }
+        if let Some((idx, mut classes)) = classes {
+            classes.extend(quote!(.close_attr()));
+            expanded[idx] = classes;
+        }
+        let attrs = expanded;
 
         let body = children
             .peek()
@@ -425,6 +492,7 @@ impl Element {
             .then(|| quote!(.body(::htmx::Fragment(|mut __html: &mut ::htmx::Html| {#(#children)*}), #close_arg))).unwrap_or_else(|| quote!(.close(#close_arg)));
 
         quote!({{
+            use ::htmx::__private::{CloseAttr as _, ForwardAttrs as _};
             let mut __html = #name;
             #(let __html = __html #attrs;)*
             __html
@@ -516,13 +584,13 @@ impl Name {
 
     fn attribute(input: ParseStream) -> syn::Result<Self> {
         input.parse().and_then(|value: Self| {
-            if let Some(value) = value.lit_str() {
+            if let Some(key) = value.lit_str() {
                 ensure!(
-                    value.chars().any(|c| c.is_whitespace()
+                    !key.chars().any(|c| c.is_whitespace()
                         || c.is_control()
                         || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')),
                     value,
-                    "invalid key `{value}`, \
+                    "invalid key `{key}`, \
                     https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0"
                 );
             }
@@ -582,9 +650,10 @@ enum Attr {
     Id(Token![#], Name),
     Classes(Classes),
     // TODO Value(Expr),
-    // TODO Flag(Name),
-    // TODO StructShorthand(Name),
-    KeyValue(Name, Token![:], Expr),
+    // `input(value)` cannot be a shorthand for `input(value: value)`, as it is
+    // a flag.
+    Flag(AttributeKey),
+    KeyValue(AttributeKey, Token![:], Expr),
     Trailing(Token![..], Expr),
 }
 
@@ -599,30 +668,25 @@ impl Parse for Attr {
         } else if input.peek(Token![.]) {
             Self::Classes(input.parse()?)
         } else {
-            // let expr = input.parse()?;
-            // if input.peek(Token![:]) {
-            Self::KeyValue(input.call(Name::attribute)?, input.parse()?, input.parse()?)
-            // } else {
-            // Self::Value(expr)
-            // }
+            let key = input.call(attribute_key)?;
+            if input.peek(Token![:]) {
+                Self::KeyValue(key, input.parse()?, input.parse()?)
+            } else {
+                Self::Flag(key)
+            }
         })
     }
 }
 
-fn is_keyword(ident: &Ident) -> bool {
-    match ident.to_string().as_str() {
-        // Based on https://doc.rust-lang.org/1.65.0/reference/keywords.html
-        "abstract" | "as" | "async" | "await" | "become" | "box" | "break" | "const"
-        | "continue" | "crate" | "do" | "dyn" | "else" | "enum" | "extern" | "false" | "final"
-        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod"
-        | "move" | "mut" | "override" | "priv" | "pub" | "ref" | "return" | "Self" | "self"
-        | "static" | "struct" | "super" | "trait" | "true" | "try" | "type" | "typeof"
-        | "unsafe" | "unsized" | "use" | "virtual" | "where" | "while" | "yield" => true,
-        _ => false,
+impl Attr {
+    fn is_class(&self) -> bool {
+        match self {
+            Attr::Classes(_) => true,
+            Attr::KeyValue(AttributeKey::Fn(name), ..) => name == "class",
+            _ => false,
+        }
     }
-}
 
-impl Attr {
     fn expand(self) -> TokenStream {
         match self {
             Attr::Id(_, id) => quote!(.id(#id)),
@@ -630,23 +694,44 @@ impl Attr {
                 let classes = classes.classes.into_iter();
                 quote!(#(.class(#classes))*)
             }
-            Attr::KeyValue(name, _, value) => match name {
-                Name::Ident(ref name) if is_keyword(name) => {
-                    let name = format_ident!("{name}_");
-                    quote!(.#name(#value))
-                }
-                Name::Ident(name) => quote!(.#name(#value)),
-                name => {
-                    if name.lit_str().is_some() {
-                        quote!(.custom_attr_unchecked(#name, #value))
-                    } else {
-                        quote!(.custom_attr(#name, #value))
-                    }
-                }
-            },
-            Attr::Trailing(..) => todo!(),
+            Attr::Flag(key) => Attribute { key, value: None }.into_token_stream(),
+            Attr::KeyValue(key, _, value) => Attribute {
+                key,
+                value: Some(value.into_token_stream()),
+            }
+            .into_token_stream(),
+            Attr::Trailing(_, attrs) => quote!(.custom_attrs(#attrs)),
         }
     }
+
+    /// Custom elements only support string keys.
+    fn into_custom(self) -> Self {
+        match self {
+            Attr::Flag(key) => Attr::Flag(key.into_custom()),
+            Attr::KeyValue(key, colon, value) => Attr::KeyValue(key.into_custom(), colon, value),
+            attr => attr,
+        }
+    }
+}
+
+/// Parses an attribute key, translated the same way as in `html!`, e.g.,
+/// `hx::get` to `hx-get`.
+fn attribute_key(input: ParseStream) -> syn::Result<AttributeKey> {
+    if input.peek(LitStr) || input.peek(Brace) {
+        return Ok(match input.call(Name::attribute)? {
+            Name::Block(block) if parse2::<LitStr>(block.content.clone()).is_err() => {
+                AttributeKey::Expr(block.into_token_stream())
+            }
+            name => AttributeKey::from_str(name.lit_str().expect("name is a string"), name.span())?,
+        });
+    }
+    let mut path = Path::from(input.call(Ident::parse_any)?);
+    while input.peek(Token![::]) {
+        path.segments.push_punct(input.parse()?);
+        path.segments
+            .push_value(input.call(Ident::parse_any)?.into());
+    }
+    AttributeKey::from_path(&path).map_err(Into::into)
 }
 
 #[cfg(test)]
@@ -670,4 +755,11 @@ mod test {
             assert_tokens!(rest.into_token_stream(), {$after});
         }}
     }
+
+    #[test]
+    fn attribute_names() {
+        rtml(quote!(div(class: "x", "data-x": 1))).unwrap();
+        let error = format!("{:?}", rtml(quote!(div("a b": "x"))).unwrap_err());
+        assert!(error.contains("invalid key `a b`"), "{error}");
+    }
 }
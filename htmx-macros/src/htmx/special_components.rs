@@ -71,11 +71,14 @@ pub enum Special {
     If(If),
     For(For),
     While(While),
+    Loop(Loop),
+    Jump(Jump),
+    Let(Let),
     FunctionCall(FunctionCall),
 }
 
 fn map_vec(value: Vec<Node>) -> Result<Vec<super::Node>> {
-    value.into_iter().map(super::Node::try_from).collect()
+    super::try_into_nodes(value)
 }
 
 impl TryFrom<Special> for super::Node {
@@ -95,6 +98,26 @@ impl TryFrom<Special> for super::Node {
                 expr: expr.into_token_stream(),
                 body: map_vec(body)?,
             }),
+            Special::Loop(Loop { body, .. }) => super::Node::Loop(super::Loop {
+                body: map_vec(body)?,
+            }),
+            Special::Jump(Jump::Break { break_token, .. }) => {
+                super::Node::Jump(quote_spanned!(break_token.span=> #break_token;))
+            }
+            Special::Jump(Jump::Continue { continue_token, .. }) => {
+                super::Node::Jump(quote_spanned!(continue_token.span=> #continue_token;))
+            }
+            Special::Let(Let {
+                pat,
+                expr,
+                else_branch,
+                ..
+            }) => super::Node::Let(super::Let {
+                pat: pat.into_token_stream(),
+                expr: expr.into_token_stream(),
+                else_branch: map_vec(else_branch)?,
+                body: Vec::new(),
+            }),
             Special::FunctionCall(FunctionCall { function, args, .. }) => {
                 super::Node::FunctionCall(super::FunctionCall {
                     function: function.into_token_stream(),
@@ -112,6 +135,7 @@ impl Special {
             Special::For(for_) => for_.expand_node(),
             Special::While(while_) => while_.expand_node(),
             Special::FunctionCall(function_call) => function_call.expand_node(),
+            Special::Loop(_) | Special::Jump(_) | Special::Let(_) => todo!("{}", line!()),
         }
     }
 }
@@ -126,6 +150,10 @@ impl CustomNode for Special {
         input.peek(Token![if])
             || input.peek(Token![for])
             || input.peek(Token![while])
+            || input.peek(Token![loop])
+            || input.peek(Token![break])
+            || input.peek(Token![continue])
+            || input.peek(Token![let])
             || fork.parse::<Token![<]>().is_ok()
                 && fork.parse::<ExprPath>().is_ok()
                 && fork.peek(Paren)
@@ -136,6 +164,11 @@ impl CustomNode for Special {
             () if input.peek(Token![if]) => parser.parse_recoverable(input).map(Self::If),
             () if input.peek(Token![for]) => parser.parse_recoverable(input).map(Self::For),
             () if input.peek(Token![while]) => parser.parse_recoverable(input).map(Self::While),
+            () if input.peek(Token![loop]) => parser.parse_recoverable(input).map(Self::Loop),
+            () if input.peek(Token![break]) || input.peek(Token![continue]) => {
+                parser.parse_recoverable(input).map(Self::Jump)
+            }
+            () if input.peek(Token![let]) => parser.parse_recoverable(input).map(Self::Let),
             () if input.peek(Token![<]) => parser.parse_recoverable(input).map(Self::FunctionCall),
             _ => unreachable!("`peek_element` should only peek valid keywords"),
         }
@@ -341,6 +374,88 @@ impl ParseRecoverable for While {
     }
 }
 
+#[derive(Debug, ToTokens)]
+pub struct Loop {
+    pub loop_token: Token![loop],
+    #[syn(braced)]
+    pub brace: Brace,
+    #[syn(in = brace)]
+    #[to_tokens(TokenStreamExt::append_all)]
+    pub body: Vec<Node>,
+}
+
+impl ParseRecoverable for Loop {
+    fn parse_recoverable(parser: &mut RecoverableContext, input: ParseStream) -> Option<Self> {
+        let body;
+        Some(Self {
+            loop_token: parser.parse_simple(input)?,
+            brace: braced!(body in parser, input),
+            body: parse_nodes(parser, body),
+        })
+    }
+}
+
+/// `break` or `continue`, only valid inside the body of a `for`, `while` or
+/// `loop`, but not inside an element in it.
+#[derive(Debug, ToTokens)]
+pub enum Jump {
+    Break {
+        break_token: Token![break],
+        semi_token: Option<Token![;]>,
+    },
+    Continue {
+        continue_token: Token![continue],
+        semi_token: Option<Token![;]>,
+    },
+}
+
+impl ParseRecoverable for Jump {
+    fn parse_recoverable(parser: &mut RecoverableContext, input: ParseStream) -> Option<Self> {
+        Some(if input.peek(Token![break]) {
+            Self::Break {
+                break_token: parser.parse_simple(input)?,
+                semi_token: parser.parse_simple(input)?,
+            }
+        } else {
+            Self::Continue {
+                continue_token: parser.parse_simple(input)?,
+                semi_token: parser.parse_simple(input)?,
+            }
+        })
+    }
+}
+
+#[derive(Debug, ToTokens)]
+pub struct Let {
+    pub let_token: Token![let],
+    pub pat: syn::Pat,
+    pub eq_token: Token![=],
+    pub expr: Expr,
+    pub else_token: Token![else],
+    #[syn(braced)]
+    pub brace: Brace,
+    #[syn(in = brace)]
+    #[to_tokens(TokenStreamExt::append_all)]
+    pub else_branch: Vec<Node>,
+    pub semi_token: Token![;],
+}
+
+impl ParseRecoverable for Let {
+    fn parse_recoverable(parser: &mut RecoverableContext, input: ParseStream) -> Option<Self> {
+        let else_branch;
+        Some(Self {
+            let_token: parser.parse_simple(input)?,
+            pat: parser.save_diagnostics(syn::Pat::parse_single(input))?,
+            eq_token: parser.parse_simple(input)?,
+            expr: parser.save_diagnostics(Expr::parse_without_eager_brace(input))?,
+            else_token: parser.parse_simple(input)?,
+            brace: braced!(else_branch in parser, input),
+            else_branch: parse_nodes(parser, else_branch),
+            semi_token: parser.parse_simple(input)?,
+        })
+    }
+}
+
 #[derive(Debug, ToTokens)]
 pub struct FunctionCall {
     pub open_token: Token![<],
@@ -25,7 +25,13 @@ pub use htmx::html::html;
 #[manyhow(proc_macro)]
 pub use htmx::rusty::rtml;
 
-// js!{  }
+#[manyhow(proc_macro)]
+pub fn js(input: TokenStream) -> Result {
+    use htmx_script::{Script, ToJs};
+    let script: Script = syn::parse2(input)?;
+    let script = script.to_java_script();
+    Ok(quote!(::htmx::Script(::std::borrow::Cow::Owned(#script))))
+}
 
 mod css;
 #[manyhow(proc_macro)]
@@ -35,7 +41,7 @@ mod component;
 #[manyhow(item_as_dummy, proc_macro_attribute)]
 pub use component::component;
 
-#[manyhow(proc_macro_derive(WriteHtml))]
+#[manyhow(proc_macro_derive(WriteHtml, attributes(html)))]
 pub fn write_html(
     syn::ItemStruct {
         ident,
@@ -44,15 +50,25 @@ pub fn write_html(
         ..
     }: syn::ItemStruct,
 ) -> Result {
-    let Some(field) = fields.iter().next() else {
+    // The buffer is the field marked `#[html]`, or the first one.
+    let mut marked = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("html")));
+    let Some((index, field)) = marked.next().or_else(|| fields.iter().enumerate().next()) else {
         bail!(fields, "field for html buffer required for WriteHtml")
     };
+    if let Some((_, duplicate)) = marked.next() {
+        bail!(
+            duplicate,
+            "only one field can be marked as `#[html]` buffer"
+        );
+    }
     let field_type = &field.ty;
-    let field = field
-        .ident
-        .as_ref()
-        .map(ToTokens::to_token_stream)
-        .unwrap_or_else(|| quote!(0));
+    let field = field.ident.as_ref().map_or_else(
+        || syn::Index::from(index).into_token_stream(),
+        ToTokens::to_token_stream,
+    );
     let where_clause = generics.make_where_clause();
     where_clause.predicates.push(parse_quote!(#field_type: ::htmx::WriteHtml));
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
@@ -1,6 +1,7 @@
 use std::fmt::Write;
 use std::{iter, mem};
 
+use proc_macro2::{TokenStream, TokenTree};
 use quote::ToTokens;
 use quote_use::quote_use as quote;
 use syn::parse::discouraged::Speculative;
@@ -16,27 +17,38 @@ fn Ok<T>(t: T) -> Result<T> {
 #[macro_use]
 mod macros;
 
+mod kw {
+    syn::custom_keyword!(js);
+    syn::custom_keyword!(null);
+    syn::custom_keyword!(undefined);
+}
+
 pub enum JsToken {
     Verbatum(String),
     Rust(Ident),
 }
 
-pub struct JsTokens(Vec<JsToken>);
+pub struct JsTokens {
+    tokens: Vec<JsToken>,
+    /// Number of lowered `match`es, used to name their temporaries uniquely.
+    matches: usize,
+}
+
 impl JsTokens {
     fn verbatum(&mut self, value: impl Into<String>) {
-        self.0.push(JsToken::Verbatum(value.into()))
+        self.tokens.push(JsToken::Verbatum(value.into()))
     }
 
     fn rust(&mut self, value: Ident) {
-        self.0.push(JsToken::Rust(value))
+        self.tokens.push(JsToken::Rust(value))
     }
 }
 
 impl ToTokens for JsTokens {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
         let mut js_tokens = Vec::new();
         let mut last_verbatum = String::new();
-        for token in &self.0 {
+        for token in &self.tokens {
             match token {
                 JsToken::Verbatum(token) => write!(last_verbatum, " {token}").unwrap(),
                 JsToken::Rust(ident) => {
@@ -60,7 +72,10 @@ impl ToTokens for JsTokens {
 
 pub trait ToJs {
     fn to_java_script(&self) -> JsTokens {
-        let mut s = JsTokens(Vec::new());
+        let mut s = JsTokens {
+            tokens: Vec::new(),
+            matches: 0,
+        };
         self.to_js(&mut s);
         s
     }
@@ -105,9 +120,47 @@ impl<T: ToJs> ToJs for Punctuated<T, T![,]> {
 
 impl ToJs for Lit {
     fn to_js(&self, js: &mut JsTokens) {
-        // TODO ensure literal valid in js
-        self.to_token_stream().to_string().to_js(js)
+        match self {
+            Lit::Str(s) => js_string(&s.value()).to_js(js),
+            Lit::Char(c) => js_string(&c.value().to_string()).to_js(js),
+            Lit::Byte(b) => b.value().to_string().to_js(js),
+            Lit::ByteStr(b) => {
+                "[".to_js(js);
+                for b in b.value() {
+                    b.to_string().to_js(js);
+                    ",".to_js(js);
+                }
+                "]".to_js(js);
+            }
+            // `base10_digits` excludes suffixes like `u32`.
+            Lit::Int(i) => i.base10_digits().to_js(js),
+            Lit::Float(f) => f.base10_digits().to_js(js),
+            Lit::Bool(b) => b.value.to_string().to_js(js),
+            _ => self.to_token_stream().to_string().to_js(js),
+        }
+    }
+}
+
+/// Escapes `value` as a JSON string literal, which is valid in JS as well.
+fn js_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            // Line separators are not allowed in strings before ES2019.
+            c if c.is_control() || matches!(c, '\u{2028}' | '\u{2029}') => {
+                write!(out, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
     }
+    out.push('"');
+    out
 }
 
 pub struct Script(pub Vec<Stmt>);
@@ -131,6 +184,12 @@ impl Parse for Script {
 pub enum Stmt {
     Binding(Binding),
     Item(Item),
+    For(For),
+    While(While),
+    Match(ExprMatch, Option<T![;]>),
+    Return(Return),
+    Break(T![break], Option<T![;]>),
+    Continue(T![continue], Option<T![;]>),
     Expr(Expr, Option<T![;]>),
 }
 
@@ -139,6 +198,13 @@ impl ToJs for Stmt {
         match self {
             Stmt::Binding(b) => b.to_js(js),
             Stmt::Item(i) => i.to_js(js),
+            Stmt::For(f) => f.to_js(js),
+            Stmt::While(w) => w.to_js(js),
+            Stmt::Match(m, None) => m.lower(js, MatchValue::Return),
+            Stmt::Match(m, Some(_)) => m.lower(js, MatchValue::Discard),
+            Stmt::Return(r) => r.to_js(js),
+            Stmt::Break(..) => "break;".to_js(js),
+            Stmt::Continue(..) => "continue;".to_js(js),
             Stmt::Expr(e, None) => {
                 "return".to_js(js);
                 e.to_js(js);
@@ -158,12 +224,111 @@ impl Parse for Stmt {
             input.parse().map(Self::Binding)
         } else if input.peek(T![fn]) {
             input.parse().map(Self::Item)
+        } else if input.peek(T![for]) {
+            input.parse().map(Self::For)
+        } else if input.peek(T![while]) {
+            input.parse().map(Self::While)
+        } else if input.peek(T![match]) {
+            let match_ = input.parse()?;
+            let semi: Option<T![;]> = input.parse()?;
+            // Like in Rust, a `match` followed by further statements needs no
+            // `;`, but only a trailing one evaluates to the block's value.
+            let semi = semi.or_else(|| (!input.is_empty()).then(Default::default));
+            Ok(Self::Match(match_, semi))
+        } else if input.peek(T![return]) {
+            input.parse().map(Self::Return)
+        } else if input.peek(T![break]) {
+            Ok(Self::Break(input.parse()?, input.parse()?))
+        } else if input.peek(T![continue]) {
+            Ok(Self::Continue(input.parse()?, input.parse()?))
         } else {
             Ok(Self::Expr(input.parse()?, input.parse()?))
         }
     }
 }
 
+pub struct For {
+    pub for_: T![for],
+    pub pat: Pat,
+    pub in_: T![in],
+    pub expr: Expr,
+    pub body: Block,
+}
+
+impl ToJs for For {
+    fn to_js(&self, js: &mut JsTokens) {
+        "for (const".to_js(js);
+        self.pat.to_js(js);
+        "of".to_js(js);
+        self.expr.to_js(js);
+        ")".to_js(js);
+        self.body.loop_body_to_js(js);
+    }
+}
+
+impl Parse for For {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            for_: input.parse()?,
+            pat: input.parse()?,
+            in_: input.parse()?,
+            expr: Expr::parse_before_block(input)?,
+            body: input.parse()?,
+        })
+    }
+}
+
+pub struct Return {
+    pub return_: T![return],
+    pub expr: Option<Expr>,
+    pub semi: Option<T![;]>,
+}
+
+impl ToJs for Return {
+    fn to_js(&self, js: &mut JsTokens) {
+        "return".to_js(js);
+        self.expr.to_js(js);
+        ";".to_js(js);
+    }
+}
+
+impl Parse for Return {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            return_: input.parse()?,
+            expr: (!(input.is_empty() || input.peek(T![;])))
+                .then(|| input.parse())
+                .transpose()?,
+            semi: input.parse()?,
+        })
+    }
+}
+
+pub struct While {
+    pub while_: T![while],
+    pub cond: Expr,
+    pub body: Block,
+}
+
+impl ToJs for While {
+    fn to_js(&self, js: &mut JsTokens) {
+        "while (".to_js(js);
+        self.cond.to_js(js);
+        ")".to_js(js);
+        self.body.loop_body_to_js(js);
+    }
+}
+
+impl Parse for While {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            while_: input.parse()?,
+            cond: Expr::parse_before_block(input)?,
+            body: input.parse()?,
+        })
+    }
+}
+
 // Stupid idea, we could consider https://stackoverflow.com/a/16719348/10519515
 
 // TODO: keep in mind, that js allows assigning invalid things sometime
@@ -182,14 +347,38 @@ pub struct Binding {
 
 impl ToJs for Binding {
     fn to_js(&self, js: &mut JsTokens) {
-        match self.kind {
+        let kind = match self.kind {
             Some(BindingKind::Pub(_)) => "var",
             Some(BindingKind::Mut(_)) => "let",
             None => "const",
+        };
+        match &self.init {
+            Some(BindingInit::Match(_, m)) => {
+                // The arms assign to a temporary, as neither a `const` nor a
+                // destructuring can be declared without initializer. It is a
+                // `var`, as the numbering restarts in every script, and `var`s
+                // can be redeclared at the top level, unlike `let`s.
+                let value = format!("__match{}_value", js.matches);
+                "var".to_js(js);
+                value.to_js(js);
+                ";".to_js(js);
+                m.lower(js, MatchValue::Assign(&value));
+                kind.to_js(js);
+                self.pat.to_js(js);
+                "=".to_js(js);
+                value.to_js(js);
+            }
+            Some(BindingInit::Expr(_, expr)) => {
+                kind.to_js(js);
+                self.pat.to_js(js);
+                "=".to_js(js);
+                expr.to_js(js);
+            }
+            None => {
+                kind.to_js(js);
+                self.pat.to_js(js);
+            }
         }
-        .to_js(js);
-        self.pat.to_js(js);
-        self.init.to_js(js);
         ";".to_js(js)
     }
 }
@@ -229,6 +418,8 @@ pub enum Pat {
     Ident(Ident),
     Tuple(PatTuple),
     Struct(PatStruct),
+    Lit(Lit),
+    Wild(T![_]),
     // Rest(ColonColon)
 }
 
@@ -238,12 +429,19 @@ impl ToJs for Pat {
             Pat::Ident(i) => i.to_js(js),
             Pat::Tuple(t) => t.to_js(js),
             Pat::Struct(s) => s.to_js(js),
+            Pat::Lit(l) => l.to_js(js),
+            Pat::Wild(_) => "_".to_js(js),
         }
     }
 }
 
 impl Parse for Pat {
     fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(T![_]) {
+            return input.parse().map(Self::Wild);
+        } else if input.peek(Lit) {
+            return input.parse().map(Self::Lit);
+        }
         input
             .parse()
             .map(Self::Ident)
@@ -253,21 +451,23 @@ impl Parse for Pat {
                     .map(Self::Tuple)
                     .or_else(|_| input.parse().map(Self::Struct))
             })
-            .map_err(|_| input.error("Expected ident, `(...)`, or `{..}`"))
+            .map_err(|_| input.error("Expected ident, literal, `_`, `(...)`, or `{..}`"))
     }
 }
 
 pub struct PatTuple {
     pub delimiter: TupleDelimiter,
     pub elems: Punctuated<Pat, T![,]>,
-    // TODO rest `..`
+    pub rest: Option<PatRest>,
 }
 
 impl ToJs for PatTuple {
     fn to_js(&self, js: &mut JsTokens) {
-        "(".to_js(js);
+        // Tuples are arrays in JS.
+        "[".to_js(js);
         self.elems.to_js(js);
-        ")".to_js(js);
+        self.rest.to_js(js);
+        "]".to_js(js);
     }
 }
 
@@ -281,9 +481,11 @@ impl Parse for PatTuple {
         } else {
             return Err(input.error("expected `[...]` or `(...)`"));
         };
+        let (elems, rest) = PatRest::parse_terminated(&elems)?;
         Ok(Self {
             delimiter,
-            elems: elems.parse_terminated(Pat::parse, T![,])?,
+            elems,
+            rest,
         })
     }
 }
@@ -296,13 +498,14 @@ pub enum TupleDelimiter {
 pub struct PatStruct {
     pub brace: T![{}],
     pub fields: Punctuated<FieldPat, T![,]>,
-    // TODO rest `..`
+    pub rest: Option<PatRest>,
 }
 
 impl ToJs for PatStruct {
     fn to_js(&self, js: &mut JsTokens) {
         "{".to_js(js);
         self.fields.to_js(js);
+        self.rest.to_js(js);
         "}".to_js(js);
     }
 }
@@ -310,16 +513,71 @@ impl ToJs for PatStruct {
 impl Parse for PatStruct {
     fn parse(input: ParseStream) -> Result<Self> {
         let content;
+        let brace = braced!(content in input);
+        let (fields, rest) = PatRest::parse_terminated(&content)?;
         Ok(PatStruct {
-            brace: braced!(content in input),
-            fields: content.parse_terminated(FieldPat::parse, T![,])?,
+            brace,
+            fields,
+            rest,
         })
     }
 }
 
+/// Rest pattern `..` or `..ident`, which collects the remaining elements or
+/// fields into `ident`.
+pub struct PatRest {
+    pub dot2: T![..],
+    pub ident: Option<Ident>,
+}
+
+impl ToJs for PatRest {
+    fn to_js(&self, js: &mut JsTokens) {
+        // JS ignores remaining elements by default.
+        if let Some(ident) = &self.ident {
+            "...".to_js(js);
+            ident.to_js(js);
+        }
+    }
+}
+
+impl Parse for PatRest {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            dot2: input.parse()?,
+            ident: input.peek(Ident).then(|| input.parse()).transpose()?,
+        })
+    }
+}
+
+impl PatRest {
+    /// Parses comma separated `T`s, optionally ending with a rest pattern.
+    fn parse_terminated<T: Parse>(
+        input: ParseStream,
+    ) -> Result<(Punctuated<T, T![,]>, Option<Self>)> {
+        let mut elems = Punctuated::new();
+        while !input.is_empty() {
+            if input.peek(T![..]) {
+                let rest = input.parse()?;
+                input.parse::<Option<T![,]>>()?;
+                if !input.is_empty() {
+                    return Err(input.error("rest pattern `..` needs to be last"));
+                }
+                return Ok((elems, Some(rest)));
+            }
+            elems.push_value(input.parse()?);
+            if input.is_empty() {
+                break;
+            }
+            elems.push_punct(input.parse()?);
+        }
+        Ok((elems, None))
+    }
+}
+
 pub struct FieldPat {
     pub member: Ident,
     pub pat: Option<(T![:], Box<Pat>)>,
+    pub default: Option<(T![=], Box<Expr>)>,
 }
 
 impl ToJs for FieldPat {
@@ -329,6 +587,10 @@ impl ToJs for FieldPat {
             ":".to_js(js);
             pat.to_js(js);
         }
+        if let Some((_, default)) = &self.default {
+            "=".to_js(js);
+            default.to_js(js);
+        }
     }
 }
 
@@ -340,29 +602,30 @@ impl Parse for FieldPat {
                 .peek(T![:])
                 .then(|| Ok((input.parse()?, input.parse()?)))
                 .transpose()?,
+            default: input
+                .peek(T![=])
+                .then(|| Ok((input.parse()?, input.parse()?)))
+                .transpose()?,
         })
     }
 }
 
-pub struct BindingInit {
-    pub eq: T![=],
-    pub expr: Box<Expr>,
+pub enum BindingInit {
+    Expr(T![=], Box<Expr>),
+    /// `match` is lowered to statements, so it is only supported directly as
+    /// initializer.
+    Match(T![=], ExprMatch),
     // diverge,
     // I think this could be implemented using a try catch
 }
 
-impl ToJs for BindingInit {
-    fn to_js(&self, js: &mut JsTokens) {
-        "=".to_js(js);
-        self.expr.to_js(js);
-    }
-}
-
 impl Parse for BindingInit {
     fn parse(input: ParseStream) -> Result<Self> {
-        Ok(Self {
-            eq: input.parse()?,
-            expr: input.parse()?,
+        let eq = input.parse()?;
+        Ok(if input.peek(T![match]) {
+            Self::Match(eq, input.parse()?)
+        } else {
+            Self::Expr(eq, input.parse()?)
         })
     }
 }
@@ -371,17 +634,22 @@ pub enum Expr {
     // Let's be lazy and let js figure out precedence
     Op(Box<Expr>, Op, Box<Expr>),
     Unary(ExprUnary),
-    // TODO support template strings, idea: __""
     Lit(Lit),
-    Format(T![$], LitStr),
+    Null(kw::null),
+    Undefined(kw::undefined),
+    Format(ExprFormat),
     Block(Block),
     Variable(Ident),
     RustReference(RustReference),
     Paren(ExprParen),
     Call(ExprCall),
     Field(ExprField),
+    Index(ExprIndex),
     Tuple(ExprTuple),
     Struct(ExprStruct),
+    Assign(ExprAssign),
+    Update(ExprUpdate),
+    Raw(ExprRaw),
 }
 
 impl ToJs for Expr {
@@ -394,53 +662,80 @@ impl ToJs for Expr {
             }
             Expr::Unary(u) => u.to_js(js),
             Expr::Lit(l) => l.to_js(js),
-            Expr::Format(_, lit) => {
-                let lit = lit.value();
-                format!("`{}`", lit.replace('`', "\\`")).to_js(js);
-            }
+            Expr::Null(_) => "null".to_js(js),
+            Expr::Undefined(_) => "undefined".to_js(js),
+            Expr::Format(f) => f.to_js(js),
             Expr::Block(b) => b.to_js(js),
             Expr::Variable(i) => i.to_js(js),
             Expr::RustReference(r) => r.to_js(js),
             Expr::Paren(p) => p.to_js(js),
             Expr::Call(c) => c.to_js(js),
             Expr::Field(f) => f.to_js(js),
+            Expr::Index(i) => i.to_js(js),
             Expr::Tuple(t) => t.to_js(js),
             Expr::Struct(s) => s.to_js(js),
+            Expr::Assign(a) => a.to_js(js),
+            Expr::Update(u) => u.to_js(js),
+            Expr::Raw(r) => r.to_js(js),
         }
     }
 }
 
 impl Expr {
     fn lhs(input: ParseStream) -> Result<Self> {
-        Ok(if input.peek(T![!]) || input.peek(T![-]) {
-            Self::Unary(input.parse()?)
-        } else if input.peek(Lit) {
-            Self::Lit(input.parse()?)
-        } else if input.peek(T![$]) && input.peek2(Lit) {
-            Self::Format(input.parse()?, input.parse()?)
-        } else if input.peek(T![{}]) {
-            Self::Block(input.parse()?)
-        } else if input.peek(Ident) {
-            Self::Variable(input.parse()?)
-        } else if input.peek(T![$]) {
-            Self::RustReference(input.parse()?)
-        } else if input.peek(T![()]) {
-            tuple_or_paren(input)?
-        } else {
-            return Err(input.error("expected expression"));
-        })
+        Ok(
+            if input.peek(T![!]) || input.peek(T![-]) || input.peek(T![typeof]) {
+                Self::Unary(input.parse()?)
+            } else if input.peek(Lit) {
+                Self::Lit(input.parse()?)
+            } else if input.peek(kw::null) {
+                Self::Null(input.parse()?)
+            } else if input.peek(kw::undefined) {
+                Self::Undefined(input.parse()?)
+            } else if input.peek(T![$]) && input.peek2(Lit) {
+                Self::Format(input.parse()?)
+            } else if input.peek(T![{}]) {
+                Self::Block(input.parse()?)
+            } else if input.peek(T![match]) {
+                return Err(input.error(
+                    "`match` is only supported as statement, `let` initializer or match arm",
+                ));
+            } else if input.peek(kw::js) && input.peek2(T![!]) {
+                Self::Raw(input.parse()?)
+            } else if input.peek(Ident) {
+                Self::Variable(input.parse()?)
+            } else if input.peek(T![$]) {
+                Self::RustReference(input.parse()?)
+            } else if input.peek(T![()]) {
+                tuple_or_paren(input)?
+            } else {
+                return Err(input.error("expected expression"));
+            },
+        )
     }
 
-    fn parse(self, input: ParseStream) -> Result<Self> {
-        match () {
-            _ if input.is_empty() || input.peek(T![,]) || input.peek(T![;]) => Ok(self),
+    /// Parses an expression followed by a block, e.g., the scrutinee of
+    /// `match` or the condition of `while`.
+    fn parse_before_block(input: ParseStream) -> Result<Self> {
+        let mut expr = TokenStream::new();
+        while !input.peek(T![{}]) {
+            if input.is_empty() {
+                return Err(input.error("expected `{`"));
+            }
+            expr.extend([input.parse::<TokenTree>()?]);
+        }
+        syn::parse2(expr)
+    }
 
+    /// Parses field accesses, calls and indexing following `self`.
+    fn postfix(self, input: ParseStream) -> Result<Self> {
+        match () {
             _ if input.peek(T![.]) => Self::Field(ExprField {
                 expr: self.into(),
                 dot: input.parse()?,
                 field: input.parse()?,
             })
-            .parse(input),
+            .postfix(input),
 
             _ if input.peek(T![()]) => {
                 let params;
@@ -449,11 +744,51 @@ impl Expr {
                     paren: parenthesized!(params in input),
                     params: Punctuated::parse_terminated(&params)?,
                 })
-                .parse(input)
+                .postfix(input)
             }
 
+            _ if input.peek(T![[]]) => {
+                let index;
+                Self::Index(ExprIndex {
+                    expr: self.into(),
+                    bracket: bracketed!(index in input),
+                    index: index.parse()?,
+                })
+                .postfix(input)
+            }
+
+            _ => Ok(self),
+        }
+    }
+
+    fn parse(self, input: ParseStream) -> Result<Self> {
+        let this = self.postfix(input)?;
+        match () {
+            _ if input.is_empty() || input.peek(T![,]) || input.peek(T![;]) => Ok(this),
+
+            _ if UpdateOp::peek(input) => Self::Update(ExprUpdate {
+                expr: this.into(),
+                op: input.parse()?,
+            })
+            .parse(input),
+
+            // Compound assignments need to be checked before `Op`, as e.g. `+`
+            // also matches `+=`.
+            _ if AssignOp::peek_compound(input) => Ok(Self::Assign(ExprAssign {
+                lhs: this.into(),
+                op: input.parse()?,
+                rhs: input.parse()?,
+            })),
+
             // PRECEDENCE
-            _ if Op::peek(input) => Ok(Self::Op(self.into(), input.parse()?, input.parse()?)),
+            _ if Op::peek(input) => Ok(Self::Op(this.into(), input.parse()?, input.parse()?)),
+
+            // `==` is handled by `Op`.
+            _ if input.peek(T![=]) => Ok(Self::Assign(ExprAssign {
+                lhs: this.into(),
+                op: input.parse()?,
+                rhs: input.parse()?,
+            })),
 
             _ => Err(input.error("expected operator")),
         }
@@ -466,6 +801,73 @@ impl Parse for Expr {
     }
 }
 
+/// Template string `$"..."`, `${$ident}` interpolates the Rust value `ident`.
+pub struct ExprFormat {
+    pub dollar: T![$],
+    pub lit: LitStr,
+    pub parts: Vec<JsToken>,
+}
+
+impl ToJs for ExprFormat {
+    fn to_js(&self, js: &mut JsTokens) {
+        for part in &self.parts {
+            match part {
+                JsToken::Verbatum(verbatum) => js.verbatum(verbatum.as_str()),
+                JsToken::Rust(ident) => js.rust(ident.clone()),
+            }
+        }
+    }
+}
+
+impl Parse for ExprFormat {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let dollar = input.parse()?;
+        let lit: LitStr = input.parse()?;
+        let value = lit.value();
+        let mut parts = Vec::new();
+        let mut verbatum = String::from("`");
+        let mut rest = value.as_str();
+        while let Some(start) = rest.find("${") {
+            verbatum.push_str(&rest[..start].replace('`', "\\`"));
+            verbatum.push_str("${");
+            rest = &rest[start + 2..];
+            // Find the matching `}`, JS expressions can contain braces.
+            let mut depth = 0;
+            let end = rest
+                .find(|c| {
+                    match c {
+                        '{' => depth += 1,
+                        '}' if depth == 0 => return true,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    false
+                })
+                .ok_or_else(|| syn::Error::new(lit.span(), "unclosed `${` in template string"))?;
+            let interpolation = &rest[..end];
+            if let Some(ident) = interpolation.trim().strip_prefix('$') {
+                let mut ident: Ident = syn::parse_str(ident).map_err(|_| {
+                    syn::Error::new(
+                        lit.span(),
+                        format!("expected identifier in `${{{interpolation}}}`"),
+                    )
+                })?;
+                ident.set_span(lit.span());
+                parts.push(JsToken::Verbatum(mem::take(&mut verbatum)));
+                parts.push(JsToken::Rust(ident));
+            } else {
+                verbatum.push_str(interpolation);
+            }
+            verbatum.push('}');
+            rest = &rest[end + 1..];
+        }
+        verbatum.push_str(&rest.replace('`', "\\`"));
+        verbatum.push('`');
+        parts.push(JsToken::Verbatum(verbatum));
+        Ok(Self { dollar, lit, parts })
+    }
+}
+
 pub enum Op {
     Add(T![+]),
     Sub(T![-]),
@@ -509,10 +911,10 @@ impl Op {
             || input.peek(T![/])
             || input.peek(T![==])
             || input.peek(T![!=])
+            || input.peek(T![>=])
             || input.peek(T![>])
-            || input.peek(T![>])
-            || input.peek(T![<])
             || input.peek(T![<=])
+            || input.peek(T![<])
             || input.peek(T![&&])
             || input.peek(T![||])
     }
@@ -527,10 +929,12 @@ impl Parse for Op {
             _ if input.peek(T![/]) => Self::Div(input.parse()?),
             _ if input.peek(T![==]) => Self::Eq(input.parse()?),
             _ if input.peek(T![!=]) => Self::Ne(input.parse()?),
+            // `>=` and `<=` need to be checked first, as `>` and `<` also match
+            // their first character.
+            _ if input.peek(T![>=]) => Self::Ge(input.parse()?),
             _ if input.peek(T![>]) => Self::Gt(input.parse()?),
-            _ if input.peek(T![>]) => Self::Gt(input.parse()?),
-            _ if input.peek(T![<]) => Self::Lt(input.parse()?),
             _ if input.peek(T![<=]) => Self::Le(input.parse()?),
+            _ if input.peek(T![<]) => Self::Lt(input.parse()?),
             _ if input.peek(T![&&]) => Self::And(input.parse()?),
             _ if input.peek(T![||]) => Self::Or(input.parse()?),
             _ => return Err(input.error("expected operator")),
@@ -538,6 +942,127 @@ impl Parse for Op {
     }
 }
 
+/// Verbatim JS `js!("...")`, for things not expressible otherwise.
+///
+/// The contents are neither escaped nor validated, so they must be trusted.
+pub struct ExprRaw {
+    pub js: kw::js,
+    pub bang: T![!],
+    pub paren: T![()],
+    pub lit: LitStr,
+}
+
+impl ToJs for ExprRaw {
+    fn to_js(&self, js: &mut JsTokens) {
+        js.verbatum(self.lit.value());
+    }
+}
+
+impl Parse for ExprRaw {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit;
+        Ok(Self {
+            js: input.parse()?,
+            bang: input.parse()?,
+            paren: parenthesized!(lit in input),
+            lit: lit.parse()?,
+        })
+    }
+}
+
+/// Assignment `lhs = rhs`, also supports compound assignments like `+=`.
+pub struct ExprAssign {
+    pub lhs: Box<Expr>,
+    pub op: AssignOp,
+    pub rhs: Box<Expr>,
+}
+
+impl ToJs for ExprAssign {
+    fn to_js(&self, js: &mut JsTokens) {
+        self.lhs.to_js(js);
+        match self.op {
+            AssignOp::Assign(_) => "=",
+            AssignOp::Add(_) => "+=",
+            AssignOp::Sub(_) => "-=",
+            AssignOp::Mul(_) => "*=",
+            AssignOp::Div(_) => "/=",
+        }
+        .to_js(js);
+        self.rhs.to_js(js);
+    }
+}
+
+pub enum AssignOp {
+    Assign(T![=]),
+    Add(T![+=]),
+    Sub(T![-=]),
+    Mul(T![*=]),
+    Div(T![/=]),
+}
+
+impl AssignOp {
+    fn peek_compound(input: ParseStream) -> bool {
+        input.peek(T![+=]) || input.peek(T![-=]) || input.peek(T![*=]) || input.peek(T![/=])
+    }
+}
+
+impl Parse for AssignOp {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(match () {
+            _ if input.peek(T![+=]) => Self::Add(input.parse()?),
+            _ if input.peek(T![-=]) => Self::Sub(input.parse()?),
+            _ if input.peek(T![*=]) => Self::Mul(input.parse()?),
+            _ if input.peek(T![/=]) => Self::Div(input.parse()?),
+            _ if input.peek(T![=]) => Self::Assign(input.parse()?),
+            _ => return Err(input.error("expected assignment operator")),
+        })
+    }
+}
+
+/// Postfix increment `expr++` or decrement `expr--`.
+pub struct ExprUpdate {
+    pub expr: Box<Expr>,
+    pub op: UpdateOp,
+}
+
+impl ToJs for ExprUpdate {
+    fn to_js(&self, js: &mut JsTokens) {
+        self.expr.to_js(js);
+        match self.op {
+            UpdateOp::Increment(..) => "++",
+            UpdateOp::Decrement(..) => "--",
+        }
+        .to_js(js);
+    }
+}
+
+pub enum UpdateOp {
+    Increment(T![+], T![+]),
+    Decrement(T![-], T![-]),
+}
+
+impl UpdateOp {
+    /// Only matches at the end of an expression, to not confuse `a + +b` with
+    /// `a++`.
+    fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        fork.parse::<UpdateOp>().is_ok()
+            && (fork.is_empty() || fork.peek(T![;]) || fork.peek(T![,]))
+    }
+}
+
+impl Parse for UpdateOp {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(T![+]) && input.peek2(T![+]) {
+            Ok(Self::Increment(input.parse()?, input.parse()?))
+        } else if input.peek(T![-]) && input.peek2(T![-]) {
+            Ok(Self::Decrement(input.parse()?, input.parse()?))
+        } else {
+            Err(input.error("expected `++` or `--`"))
+        }
+    }
+}
+
 pub struct ExprUnary {
     pub op: UnaryOp,
     pub expr: Box<Expr>,
@@ -548,6 +1073,7 @@ impl ToJs for ExprUnary {
         match self.op {
             UnaryOp::Not(_) => "!",
             UnaryOp::Neg(_) => "-",
+            UnaryOp::Typeof(_) => "typeof",
         }
         .to_js(js);
         self.expr.to_js(js);
@@ -556,10 +1082,11 @@ impl ToJs for ExprUnary {
 
 impl Parse for ExprUnary {
     fn parse(input: ParseStream) -> Result<Self> {
-        // PRECEDENCE: this would result in parsing `!a || b` as `!(a || b)`
+        // Unary operators bind tighter than binary operators, i.e., `!a || b`
+        // is `(!a) || b`.
         Ok(Self {
             op: input.parse()?,
-            expr: input.parse()?,
+            expr: Expr::lhs(input)?.postfix(input)?.into(),
         })
     }
 }
@@ -567,15 +1094,18 @@ impl Parse for ExprUnary {
 pub enum UnaryOp {
     Not(T![!]),
     Neg(T![-]),
+    Typeof(T![typeof]),
 }
 
 impl Parse for UnaryOp {
     fn parse(input: ParseStream) -> Result<Self> {
         input.parse().map(Self::Not).or_else(|_| {
-            input
-                .parse()
-                .map(Self::Neg)
-                .map_err(|_| input.error("Expected `!` or `-`"))
+            input.parse().map(Self::Neg).or_else(|_| {
+                input
+                    .parse()
+                    .map(Self::Typeof)
+                    .map_err(|_| input.error("Expected `!`, `-` or `typeof`"))
+            })
         })
     }
 }
@@ -593,6 +1123,25 @@ impl ToJs for Block {
     }
 }
 
+impl Block {
+    /// Loop bodies evaluate to `()`, so their trailing expression is not
+    /// returned.
+    fn loop_body_to_js(&self, js: &mut JsTokens) {
+        "{".to_js(js);
+        for stmt in &self.stmts {
+            match stmt {
+                Stmt::Expr(expr, None) => {
+                    expr.to_js(js);
+                    ";".to_js(js);
+                }
+                Stmt::Match(m, None) => m.lower(js, MatchValue::Discard),
+                stmt => stmt.to_js(js),
+            }
+        }
+        "}".to_js(js);
+    }
+}
+
 impl Parse for Block {
     fn parse(input: ParseStream) -> Result<Self> {
         let stmts;
@@ -716,6 +1265,21 @@ impl ToJs for ExprField {
     }
 }
 
+pub struct ExprIndex {
+    pub expr: Box<Expr>,
+    pub bracket: T![[]],
+    pub index: Box<Expr>,
+}
+
+impl ToJs for ExprIndex {
+    fn to_js(&self, js: &mut JsTokens) {
+        self.expr.to_js(js);
+        "[".to_js(js);
+        self.index.to_js(js);
+        "]".to_js(js);
+    }
+}
+
 pub struct ExprStruct {
     pub brace: T![{}],
     pub fields: Punctuated<(Ident, T![:], Expr), T![,]>,
@@ -734,6 +1298,164 @@ impl ToJs for ExprStruct {
     }
 }
 
+pub struct ExprMatch {
+    pub match_: T![match],
+    pub expr: Box<Expr>,
+    pub brace: T![{}],
+    pub arms: Vec<Arm>,
+}
+
+/// How the value of a lowered `match` is used.
+#[derive(Clone, Copy)]
+enum MatchValue<'a> {
+    Return,
+    Discard,
+    Assign(&'a str),
+}
+
+impl MatchValue<'_> {
+    fn to_js(self, expr: &Expr, js: &mut JsTokens) {
+        match self {
+            MatchValue::Return => "return".to_js(js),
+            MatchValue::Discard => {}
+            MatchValue::Assign(value) => {
+                value.to_js(js);
+                "=".to_js(js);
+            }
+        }
+        expr.to_js(js);
+        ";".to_js(js);
+    }
+}
+
+impl ExprMatch {
+    /// Lowers the `match` to an `if` chain on a temporary holding the
+    /// scrutinee, inside a block scoping the temporary.
+    ///
+    /// Neither a `switch` nor a closure are used, so `break`, `continue` and
+    /// `return` in an arm affect the surrounding loop or function.
+    fn lower(&self, js: &mut JsTokens, value: MatchValue) {
+        let scrutinee = format!("__match{}", js.matches);
+        js.matches += 1;
+        "{".to_js(js);
+        "const".to_js(js);
+        scrutinee.to_js(js);
+        "=".to_js(js);
+        self.expr.to_js(js);
+        ";".to_js(js);
+        for (idx, arm) in self.arms.iter().enumerate() {
+            let binding = match arm.pats.first() {
+                Some(Pat::Ident(ident)) => Some(ident),
+                _ => None,
+            };
+            let wild = binding.is_some() || arm.pats.iter().any(|pat| matches!(pat, Pat::Wild(_)));
+            if idx > 0 {
+                "else".to_js(js);
+            }
+            if !wild {
+                "if (".to_js(js);
+                for (idx, pat) in arm.pats.iter().enumerate() {
+                    if idx > 0 {
+                        "||".to_js(js);
+                    }
+                    scrutinee.to_js(js);
+                    "===".to_js(js);
+                    pat.to_js(js);
+                }
+                ")".to_js(js);
+            }
+            arm.body_to_js(js, value, binding.map(|ident| (ident, &*scrutinee)));
+            if wild {
+                // Later arms are unreachable.
+                break;
+            }
+        }
+        "}".to_js(js);
+    }
+}
+
+impl Parse for ExprMatch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let match_ = input.parse()?;
+        let expr = Expr::parse_before_block(input)?;
+        let arms;
+        Ok(Self {
+            match_,
+            expr: expr.into(),
+            brace: braced!(arms in input),
+            arms: iter::from_fn(|| (!arms.is_empty()).then(|| arms.parse()))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+pub struct Arm {
+    pub pats: Punctuated<Pat, T![|]>,
+    pub fat_arrow: T![=>],
+    pub body: Box<Expr>,
+    pub comma: Option<T![,]>,
+}
+
+impl Arm {
+    /// Writes the body as block, `binding` is an identifier pattern bound to
+    /// the scrutinee.
+    fn body_to_js(&self, js: &mut JsTokens, value: MatchValue, binding: Option<(&Ident, &str)>) {
+        "{".to_js(js);
+        if let Some((ident, scrutinee)) = binding {
+            "const".to_js(js);
+            ident.to_js(js);
+            "=".to_js(js);
+            scrutinee.to_js(js);
+            ";".to_js(js);
+        }
+        if let Expr::Block(block) = &*self.body {
+            for stmt in &block.stmts {
+                match stmt {
+                    Stmt::Expr(expr, None) => value.to_js(expr, js),
+                    Stmt::Match(m, None) => m.lower(js, value),
+                    stmt => stmt.to_js(js),
+                }
+            }
+        } else {
+            value.to_js(&self.body, js);
+        }
+        "}".to_js(js);
+    }
+}
+
+impl Parse for Arm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pats = Punctuated::parse_separated_nonempty(input)?;
+        for pat in &pats {
+            match pat {
+                Pat::Tuple(_) | Pat::Struct(_) => {
+                    return Err(
+                        input.error("match arms only support literals, identifiers and `_`")
+                    );
+                }
+                Pat::Ident(_) if pats.len() > 1 => {
+                    return Err(input.error(
+                        "identifier patterns bind the value and cannot be combined using `|`",
+                    ));
+                }
+                _ => {}
+            }
+        }
+        let fat_arrow = input.parse()?;
+        let body = if input.peek(T![{}]) {
+            Expr::Block(input.parse()?)
+        } else {
+            input.parse()?
+        };
+        Ok(Self {
+            pats,
+            fat_arrow,
+            body: body.into(),
+            comma: input.parse()?,
+        })
+    }
+}
+
 pub enum Item {
     Fn(Fn),
 }
@@ -799,3 +1521,212 @@ fn basic() -> syn::Result<()> {
     insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
     Ok(())
 }
+
+#[test]
+fn comparison() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let x = a >= b;
+        let y = a <= b;
+    };
+    let ast: Script = parse2(rust)?;
+    let js = ast.to_java_script().to_token_stream().to_string();
+    assert!(js.contains("a >= b"), "{js}");
+    assert!(js.contains("a <= b"), "{js}");
+    Ok(())
+}
+
+#[test]
+fn match_expr() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let text = match status.code {
+            200 | 201 => "ok",
+            404 => {
+                console.log(status);
+                "not found"
+            }
+            _ => "err",
+        };
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn match_jumps() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        fn first_error(items) {
+            for item in items {
+                match item.status {
+                    200 => { continue; }
+                    0 => { break; }
+                    _ => { return item; }
+                }
+                console.log(item);
+            }
+            match items.length {
+                0 => null,
+                _ => items[0],
+            }
+        }
+    };
+    let ast: Script = parse2(rust)?;
+    let js = ast.to_java_script().to_token_stream().to_string();
+    assert!(!js.contains("=>"), "{js}");
+    assert!(!js.contains("switch"), "{js}");
+    insta::assert_snapshot!(js);
+    Ok(())
+}
+
+#[test]
+fn match_binding_alternative() {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        match code {
+            0 | other => console.log(other),
+        };
+    };
+    let error = parse2::<Script>(rust)
+        .err()
+        .expect("binding in `|` should fail");
+    assert!(error.to_string().contains("identifier patterns"), "{error}");
+}
+
+#[test]
+fn index() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let value = items[0].children()[$key][1];
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn format() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        alert($"Hi ${$name}, `${ { a: 1 }.a }` ${event.type}");
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn keywords() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let a = typeof x == "undefined";
+        let b = typeof x.y() != undefined;
+        let c = !a || b == null;
+    };
+    let ast: Script = parse2(rust)?;
+    let js = ast.to_java_script().to_token_stream().to_string();
+    assert!(
+        js.contains(r#"const a = typeof x == \"undefined\" ;"#),
+        "{js}"
+    );
+    assert!(
+        js.contains("const b = typeof x . y ( ) != undefined ;"),
+        "{js}"
+    );
+    assert!(js.contains("const c = ! a || b == null ;"), "{js}");
+    let Stmt::Binding(Binding {
+        init: Some(BindingInit::Expr(_, expr)),
+        ..
+    }) = &ast.0[0]
+    else {
+        unreachable!()
+    };
+    assert!(matches!(&**expr, Expr::Op(lhs, Op::Eq(_), _) if matches!(**lhs, Expr::Unary(_))));
+    Ok(())
+}
+
+#[test]
+fn loops() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        for x in $items {
+            console.log(x)
+        }
+        let mut i = 0;
+        while i < list.length {
+            console.log(list[i]);
+        }
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn literals() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let s = "he said \"hi\"\n";
+        let r = r#"C:\path"#;
+        let c = '\'';
+        let n = 1u32 + 0x10 + 1_000 + 2.5f64;
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn assignment() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let mut x = 0;
+        x += $delta;
+        x -= 1;
+        total = a + b;
+        i++;
+        j--;
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn destructuring() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let {a = 1, b: {c = $fallback}, ..rest} = obj;
+        let [first, (x, y), ..others] = list;
+        let (head, ..) = list;
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
+
+#[test]
+fn raw() -> syn::Result<()> {
+    use quote::quote;
+    use syn::parse2;
+    let rust = quote! {
+        let el = js!("document.querySelector('#app')?.dataset");
+        js!("label: for (;;) { break label; }");
+    };
+    let ast: Script = parse2(rust)?;
+    insta::assert_snapshot!(ast.to_java_script().to_token_stream().to_string());
+    Ok(())
+}
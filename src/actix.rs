@@ -1,13 +1,17 @@
-use std::mem;
+use std::convert::Infallible;
+use std::future::{ready, Ready};
 use std::pin::Pin;
 use std::task::Poll;
 
-use actix_web::body::{BoxBody, MessageBody};
+use actix_web::body::{BodySize, BoxBody, MessageBody};
+use actix_web::dev::Payload;
 use actix_web::http::header::ContentType;
+use actix_web::http::StatusCode;
 use actix_web::web::Bytes;
-use actix_web::{HttpResponse, Responder};
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder};
 
-use crate::{Css, Html, HtmxSrc, Fragment};
+use crate::htmx_headers::HtmxRequest;
+use crate::{Css, Fragment, Html, HtmlResponse, HtmlStream, HtmxSrc};
 
 impl Responder for Html {
     type Body = BoxBody;
@@ -25,7 +29,33 @@ impl<F: FnOnce(&mut Html)> Responder for Fragment<F> {
     fn respond_to(self, _req: &actix_web::HttpRequest) -> HttpResponse<Self::Body> {
         HttpResponse::Ok()
             .content_type(ContentType::html())
-            .body(Html::from(self))
+            .body(self.into_response_html())
+    }
+}
+
+impl Responder for HtmlResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &actix_web::HttpRequest) -> HttpResponse<Self::Body> {
+        let mut response = HttpResponse::build(
+            StatusCode::from_u16(self.status).expect("status is checked by `HtmlResponse::status`"),
+        );
+        response.content_type(ContentType::html());
+        for (name, value) in self.headers {
+            response.append_header((name.as_ref(), value));
+        }
+        response.body(self.html)
+    }
+}
+
+impl FromRequest for HtmxRequest {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Ok(Self::from_headers(|name| {
+            req.headers().get(name)?.to_str().ok()
+        })))
     }
 }
 
@@ -33,26 +63,41 @@ impl MessageBody for Html {
     type Error = <String as MessageBody>::Error;
 
     fn size(&self) -> actix_web::body::BodySize {
-        self.0.size()
+        BodySize::Sized(self.len() as u64)
     }
 
     fn poll_next(
         mut self: std::pin::Pin<&mut Self>,
         _cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Result<actix_web::web::Bytes, Self::Error>>> {
-        if self.0.is_empty() {
-            Poll::Ready(None)
-        } else {
-            let string = mem::take(&mut self.0);
-            Poll::Ready(Some(Ok(Bytes::from(string))))
-        }
+        // Every flush boundary produces a separate chunk.
+        Poll::Ready(self.next_chunk().map(|chunk| Ok(Bytes::from(chunk))))
     }
+}
 
-    fn try_into_bytes(self) -> Result<Bytes, Self>
-    where
-        Self: Sized,
-    {
-        Ok(Bytes::from(self.0))
+impl Responder for HtmlStream {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &actix_web::HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok()
+            .content_type(ContentType::html())
+            .body(self)
+    }
+}
+
+impl MessageBody for HtmlStream {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        // The next part is rendered when the previous chunk was sent.
+        Poll::Ready(self.get_mut().next().map(|chunk| Ok(Bytes::from(chunk))))
     }
 }
 
@@ -1,12 +1,14 @@
 //! Details on conversion for Attribute values.
 use std::borrow::Cow;
+use std::fmt;
 use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::num::{NonZeroU64, NonZeroU8};
 
 use derive_more::Display;
 use forr::forr;
 
-use crate::Html;
+use crate::{Html, Id, Script};
 
 /// An attribute that accepts an attribute value or a flag.
 pub struct FlagOrValue<T>(PhantomData<T>);
@@ -15,11 +17,215 @@ pub struct FlagOrValue<T>(PhantomData<T>);
 pub struct Any;
 
 /// An attribute that accepts a numeric value.
+///
+/// Numbers are written using [`Display`](std::fmt::Display), which never uses
+/// scientific notation, e.g., `1e10_f64` is written as `10000000000`. Note
+/// that `NaN` and infinite floats are not valid values.
 pub struct Number;
 
 /// An attribute that accepts a date and time.
 pub struct DateTime;
 
+/// An attribute that accepts a list of classes.
+///
+/// Besides strings, this accepts `(class, bool)` pairs, or lists of them,
+/// only adding the classes that are enabled:
+/// ```
+/// # use htmx::html;
+/// let active = true;
+/// let enabled = true;
+/// # insta::assert_display_snapshot!("doc-class",
+/// html! {
+///     <button class="btn" class=[("active", active), ("disabled", !enabled)]>"Save"</button>
+/// }
+/// # );
+/// ```
+pub struct Class;
+
+/// An attribute that accepts CSS declarations, i.e., `style`.
+///
+/// Besides strings, this accepts [`Declarations`](crate::Declarations) and
+/// `(property, value)` pairs, or lists of them, joined with `;`. Pairs whose
+/// value is `None` are skipped:
+/// ```
+/// # use htmx::html;
+/// let hidden = false;
+/// # insta::assert_display_snapshot!("doc-style",
+/// html! {
+///     <p style=[("color", Some("red")), ("display", hidden.then_some("none"))]>"Warning"</p>
+/// }
+/// # );
+/// ```
+pub struct Style;
+
+/// Link types accepted by the `rel` attribute, see
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel).
+///
+/// Multiple values are joined with spaces, and strings are accepted for other
+/// link types:
+/// ```
+/// # use htmx::attributes::Rel;
+/// # use htmx::html;
+/// # insta::assert_display_snapshot!("doc-rel",
+/// html! {
+///     <a href="https://example.com" rel=[Rel::NoOpener, Rel::NoReferrer]>"Example"</a>
+///     <link href="main.css" rel=Rel::Stylesheet/>
+/// }
+/// # );
+/// ```
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Rel {
+    #[display("alternate")]
+    Alternate,
+    #[display("author")]
+    Author,
+    #[display("bookmark")]
+    Bookmark,
+    #[display("canonical")]
+    Canonical,
+    #[display("dns-prefetch")]
+    DnsPrefetch,
+    #[display("external")]
+    External,
+    #[display("help")]
+    Help,
+    #[display("icon")]
+    Icon,
+    #[display("license")]
+    License,
+    #[display("manifest")]
+    Manifest,
+    #[display("me")]
+    Me,
+    #[display("modulepreload")]
+    ModulePreload,
+    #[display("next")]
+    Next,
+    #[display("nofollow")]
+    NoFollow,
+    #[display("noopener")]
+    NoOpener,
+    #[display("noreferrer")]
+    NoReferrer,
+    #[display("opener")]
+    Opener,
+    #[display("preconnect")]
+    Preconnect,
+    #[display("prefetch")]
+    Prefetch,
+    #[display("preload")]
+    Preload,
+    #[display("prev")]
+    Prev,
+    #[display("search")]
+    Search,
+    #[display("stylesheet")]
+    Stylesheet,
+    #[display("tag")]
+    Tag,
+}
+
+/// How content is swapped in by htmx, used for
+/// [`hx-swap`](https://htmx.org/attributes/hx-swap/).
+///
+/// Strings are accepted as well, e.g., to add modifiers like
+/// `"outerHTML swap:1s"`.
+/// ```
+/// # use htmx::attributes::{Swap, Target};
+/// # use htmx::html;
+/// # insta::assert_display_snapshot!("doc-swap",
+/// html! {
+///     <button hx::post="/clicked" hx::swap=Swap::OuterHtml>"Click"</button>
+///     <input hx::get="/search" hx::target=Target::Closest("tr".into())/>
+/// }
+/// # );
+/// ```
+///
+/// Values of other types are rejected.
+/// ```compile_fail
+/// # use htmx::attributes::Target;
+/// # use htmx::html;
+/// html! {
+///     <button hx::post="/clicked" hx::swap=Target::This>"Click"</button>
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Swap {
+    /// Replaces the inner HTML of the target.
+    #[display("innerHTML")]
+    InnerHtml,
+    /// Replaces the entire target.
+    #[display("outerHTML")]
+    OuterHtml,
+    /// Inserts before the target.
+    #[display("beforebegin")]
+    BeforeBegin,
+    /// Inserts before the first child of the target.
+    #[display("afterbegin")]
+    AfterBegin,
+    /// Inserts after the last child of the target.
+    #[display("beforeend")]
+    BeforeEnd,
+    /// Inserts after the target.
+    #[display("afterend")]
+    AfterEnd,
+    /// Deletes the target, regardless of the response.
+    #[display("delete")]
+    Delete,
+    /// Does not swap, out of band swaps are still processed.
+    #[display("none")]
+    None,
+}
+
+/// The element htmx swaps content into, used for
+/// [`hx-target`](https://htmx.org/attributes/hx-target/).
+///
+/// Strings are accepted as well, e.g., for plain CSS selectors, see [`Swap`]
+/// for an example.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Target {
+    /// The element the attribute is on.
+    This,
+    /// The closest ancestor matching the selector.
+    Closest(String),
+    /// The first child matching the selector.
+    Find(String),
+    /// The next sibling, or the next element matching the selector.
+    Next(Option<String>),
+    /// The previous sibling, or the previous element matching the selector.
+    Previous(Option<String>),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::This => f.write_str("this"),
+            Target::Closest(selector) => write!(f, "closest {selector}"),
+            Target::Find(selector) => write!(f, "find {selector}"),
+            Target::Next(None) => f.write_str("next"),
+            Target::Next(Some(selector)) => write!(f, "next {selector}"),
+            Target::Previous(None) => f.write_str("previous"),
+            Target::Previous(Some(selector)) => write!(f, "previous {selector}"),
+        }
+    }
+}
+
+/// An attribute that accepts `true` or `false` written as a value, e.g.,
+/// `aria-hidden="true"`, instead of as a flag.
+///
+/// Strings are accepted as well, for additional values like
+/// `aria-checked="mixed"`.
+pub struct BoolValue;
+
+/// An attribute that accepts `true` or `false` written as `yes` or `no`,
+/// e.g., `translate="no"`.
+///
+/// Strings are accepted as well.
+pub struct YesNo;
+
 /// An attribute that can be set as a flag or set to a value.
 #[derive(Default, Debug, PartialEq, Eq, Hash)]
 pub enum ValueOrFlag {
@@ -34,6 +240,32 @@ pub enum ValueOrFlag {
 
 /// Converts to an Attribute that accepts type `Output`, e.g.,
 /// [`Number`].
+///
+/// Values passed to attributes in [`html!`](crate::html) need to implement
+/// this for the type the attribute accepts, the error names both the value's
+/// type and the expected one:
+///
+/// ```compile_fail,E0277
+/// # use htmx::html;
+/// let content = html!("text");
+/// // `IntoHtml` content cannot be used as an attribute value.
+/// html! { <a href=content/> };
+/// ```
+///
+/// ```compile_fail,E0277
+/// # use htmx::html;
+/// // attribute expects a `Number` value, but `&str` was passed
+/// html! { <canvas width="abc"/> };
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "attribute expects a `{Output}` value, but `{Self}` was passed",
+    label = "expected a `{Output}` value",
+    note = "`Number` attributes expect numeric values, `DateTime` attributes expect \
+            `chrono::DateTime`s, `bool` attributes are flags set using `true` or `false` and \
+            `String` attributes accept strings or `char`s",
+    note = "attribute values can be `Option`s of these, content implementing `IntoHtml` is not \
+            accepted as an attribute value"
+)]
 pub trait ToAttribute<Output> {
     /// Converts into an attribute value.
     fn write(&self, html: &mut Html);
@@ -63,7 +295,7 @@ impl<A: ToAttribute<T>, T> ToAttribute<T> for Option<A> {
     }
 
     fn write_inner(&self, html: &mut Html) {
-        self.as_ref().unwrap().write(html);
+        self.as_ref().unwrap().write_inner(html);
     }
 
     fn is_unset(&self) -> bool {
@@ -104,11 +336,44 @@ into_attr! {
 
 into_attr! {  char, [char], write_attr_value_encoded, write_attr_value_inner_encoded }
 
-// /// Trait accepted by an attribute that allows both values and flags.
-// pub trait FlagOrAttributeValue {
-//     /// Converts into value.
-//     fn into_attribute(self) -> ValueOrFlag;
-// }
+into_attr! {  String, [Script<'_>], write_attr_value_encoded, write_attr_value_inner_encoded }
+
+into_attr! {  String, [Id], write_attr_value_encoded, write_attr_value_inner_encoded }
+
+into_attr! {
+    String,
+    [IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6],
+    write_attr_value_encoded,
+    write_attr_value_inner_encoded
+}
+
+/// Accepted by attributes that can be set as a flag or to a value, e.g.,
+/// `hidden` or `hidden="until-found"`.
+///
+/// `true` sets the flag, which for most of these attributes is equivalent to
+/// the empty string, e.g., `popover=""` means `popover="auto"`, `false` omits
+/// the attribute.
+#[diagnostic::on_unimplemented(
+    message = "attribute expects a flag or a string value, but `{Self}` was passed",
+    label = "expected `bool` or a string",
+    note = "use `true` to set the attribute as a flag"
+)]
+pub trait FlagOrAttributeValue: ToAttribute<FlagOrValue<String>> {}
+
+impl<T: ToAttribute<FlagOrValue<String>>> FlagOrAttributeValue for T {}
+
+/// Accepted by attributes without a specific type, e.g., custom attributes.
+///
+/// The error names the accepted values, instead of [`Any`].
+#[diagnostic::on_unimplemented(
+    message = "attribute expects a string, number or `bool` value, but `{Self}` was passed",
+    label = "expected a string, number or `bool`",
+    note = "attribute values can be `Option`s of these, content implementing `IntoHtml` is not \
+            accepted as an attribute value"
+)]
+pub trait AttributeValue: ToAttribute<Any> {}
+
+impl<T: ToAttribute<Any>> AttributeValue for T {}
 
 impl ToAttribute<bool> for bool {
     fn write(&self, _html: &mut Html) {}
@@ -140,10 +405,251 @@ impl ToAttribute<Any> for bool {
     }
 }
 
+impl ToAttribute<BoolValue> for bool {
+    fn write(&self, html: &mut Html) {
+        html.write_attr_value_unchecked(self);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        html.write_attr_value_inner_unchecked(self);
+    }
+}
+
+impl ToAttribute<YesNo> for bool {
+    fn write(&self, html: &mut Html) {
+        html.write_attr_value_unchecked(if *self { "yes" } else { "no" });
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        html.write_attr_value_inner_unchecked(if *self { "yes" } else { "no" });
+    }
+}
+
+forr! { #type:ty in [&str, String, Cow<'_, str>] #*
+    forr! { #gen:ty in [BoolValue, YesNo] #*
+        impl ToAttribute<#gen> for #type {
+            fn write(&self, html: &mut Html) {
+                html.write_attr_value_encoded(self);
+            }
+
+            fn write_inner(&self, html: &mut Html) {
+                html.write_attr_value_inner_encoded(self);
+            }
+        }
+    }
+}
+
+forr! { #type:ty in [&str, String, Cow<'_, str>, char] #*
+    impl ToAttribute<Class> for #type {
+        fn write(&self, html: &mut Html) {
+            html.write_attr_value_encoded(self);
+        }
+
+        fn write_inner(&self, html: &mut Html) {
+            html.write_attr_value_inner_encoded(self);
+        }
+    }
+}
+
+impl<C: ToAttribute<Class>> ToAttribute<Class> for (C, bool) {
+    fn write(&self, html: &mut Html) {
+        self.0.write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self.0.write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        !self.1 || self.0.is_unset()
+    }
+}
+
+impl<C: ToAttribute<Class>> ToAttribute<Class> for [(C, bool)] {
+    fn write(&self, html: &mut Html) {
+        html.write_str("=\"");
+        self.write_inner(html);
+        html.write_quote();
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        let mut first = true;
+        for class in self.iter().filter(|class| !class.is_unset()) {
+            if !first {
+                html.write_char(' ');
+            }
+            first = false;
+            class.write_inner(html);
+        }
+    }
+
+    fn is_unset(&self) -> bool {
+        self.iter().all(ToAttribute::is_unset)
+    }
+}
+
+impl<C: ToAttribute<Class>, const N: usize> ToAttribute<Class> for [(C, bool); N] {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        self[..].is_unset()
+    }
+}
+
+impl<C: ToAttribute<Class>> ToAttribute<Class> for Vec<(C, bool)> {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        self[..].is_unset()
+    }
+}
+
+into_attr! {  Rel, [Rel], write_attr_value_unchecked, write_attr_value_inner_unchecked }
+into_attr! {  Swap, [Swap], write_attr_value_unchecked, write_attr_value_inner_unchecked }
+into_attr! {  Target, [Target], write_attr_value_encoded, write_attr_value_inner_encoded }
+
+forr! { #marker:ty in [Rel, Swap, Target, Style] #*
+    forr! { #type:ty in [&str, String, Cow<'_, str>] #*
+        impl ToAttribute<#marker> for #type {
+            fn write(&self, html: &mut Html) {
+                html.write_attr_value_encoded(self);
+            }
+
+            fn write_inner(&self, html: &mut Html) {
+                html.write_attr_value_inner_encoded(self);
+            }
+        }
+    }
+}
+
+impl<P: fmt::Display, V: ToAttribute<Any>> ToAttribute<Style> for (P, V) {
+    fn write(&self, html: &mut Html) {
+        html.write_str("=\"");
+        self.write_inner(html);
+        html.write_quote();
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        html.write_attr_value_inner_encoded(&self.0);
+        html.write_char(':');
+        self.1.write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        self.1.is_unset()
+    }
+}
+
+impl<T: ToAttribute<Style>> ToAttribute<Style> for [T] {
+    fn write(&self, html: &mut Html) {
+        html.write_str("=\"");
+        self.write_inner(html);
+        html.write_quote();
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        let mut first = true;
+        for declaration in self.iter().filter(|declaration| !declaration.is_unset()) {
+            if !first {
+                html.write_char(';');
+            }
+            first = false;
+            declaration.write_inner(html);
+        }
+    }
+
+    fn is_unset(&self) -> bool {
+        self.iter().all(ToAttribute::is_unset)
+    }
+}
+
+impl<T: ToAttribute<Style>, const N: usize> ToAttribute<Style> for [T; N] {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        self[..].is_unset()
+    }
+}
+
+impl<T: ToAttribute<Style>> ToAttribute<Style> for Vec<T> {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+
+    fn is_unset(&self) -> bool {
+        self[..].is_unset()
+    }
+}
+
+impl ToAttribute<Rel> for [Rel] {
+    fn write(&self, html: &mut Html) {
+        html.write_str("=\"");
+        self.write_inner(html);
+        html.write_quote();
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        for (idx, rel) in self.iter().enumerate() {
+            if idx > 0 {
+                html.write_char(' ');
+            }
+            html.write_attr_value_inner_unchecked(rel);
+        }
+    }
+}
+
+impl<const N: usize> ToAttribute<Rel> for [Rel; N] {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+}
+
+impl ToAttribute<Rel> for Vec<Rel> {
+    fn write(&self, html: &mut Html) {
+        self[..].write(html);
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self[..].write_inner(html);
+    }
+}
+
 /// An attribute that accepts the date time according to [`<time>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/time#valid_datetime_values).
 ///
 /// The most important implementers are the [`chrono`](::chrono) types as well
 /// as the tuples for [`Year`], [`Week`] and [`Day`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid `<time datetime>` value",
+    label = "expected a date, time or duration",
+    note = "`chrono` dates, times and durations are accepted, as well as `std::time::Duration` \
+            and tuples of `Year`, `Month`, `Week` and `Day`"
+)]
 pub trait TimeDateTime {
     /// Converts into value.
     fn write(&self, html: &mut Html);
@@ -189,6 +695,34 @@ impl Week {
     }
 }
 
+/// Formats as a [duration](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#valid-duration-string),
+/// e.g., `PT1H30M`.
+///
+/// HTML only allows up to three fractional digits, so precision beyond
+/// milliseconds is truncated, a zero duration is written as `PT0S`.
+impl TimeDateTime for std::time::Duration {
+    fn write(&self, html: &mut Html) {
+        let secs = self.as_secs();
+        let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let millis = self.subsec_millis();
+        html.write_str("=\"PT");
+        if hours > 0 {
+            write!(html, "{hours}H");
+        }
+        if minutes > 0 {
+            write!(html, "{minutes}M");
+        }
+        if seconds > 0 || millis > 0 || hours == 0 && minutes == 0 {
+            write!(html, "{seconds}");
+            if millis > 0 {
+                html.write_str(format!(".{millis:03}").trim_end_matches('0'));
+            }
+            html.write_char('S');
+        }
+        html.write_quote();
+    }
+}
+
 mod chrono {
     use chrono::{
         DateTime, Duration, FixedOffset, Local, Month, NaiveDate, NaiveDateTime, NaiveTime,
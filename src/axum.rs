@@ -1,14 +1,102 @@
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use axum_core::body::boxed;
+use axum_core::extract::FromRequestParts;
 use axum_core::response::IntoResponse;
+use bytes::Bytes;
+use http::request::Parts;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
 
-use crate::{Css, Fragment, Html, HtmxSrc};
+use crate::htmx_headers::HtmxRequest;
+use crate::{Css, Fragment, Html, HtmlResponse, HtmlStream, HtmxSrc};
 
 impl IntoResponse for Html {
     fn into_response(self) -> axum_core::response::Response {
-        (
-            [("Content-Type", "text/html; charset=utf-8")],
-            self.to_string(),
-        )
-            .into_response()
+        ([("Content-Type", "text/html; charset=utf-8")], boxed(self)).into_response()
+    }
+}
+
+impl IntoResponse for HtmlResponse {
+    fn into_response(self) -> axum_core::response::Response {
+        let mut response = self.html.into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.status).expect("status is checked by `HtmlResponse::status`");
+        for (name, value) in self.headers {
+            response.headers_mut().append(
+                HeaderName::from_bytes(name.as_bytes())
+                    .expect("name is checked by `HtmlResponse::header`"),
+                HeaderValue::from_str(&value).expect("value is checked by `HtmlResponse::header`"),
+            );
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl<S: Sync> FromRequestParts<S> for HtmxRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(|name| {
+            parts.headers.get(name)?.to_str().ok()
+        }))
+    }
+}
+
+impl http_body::Body for Html {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        // Every flush boundary produces a separate chunk.
+        Poll::Ready(self.next_chunk().map(|chunk| Ok(Bytes::from(chunk))))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl IntoResponse for HtmlStream {
+    fn into_response(self) -> axum_core::response::Response {
+        ([("Content-Type", "text/html; charset=utf-8")], boxed(self)).into_response()
+    }
+}
+
+impl http_body::Body for HtmlStream {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        // The next part is rendered when the previous chunk was sent.
+        Poll::Ready(self.get_mut().next().map(|chunk| Ok(Bytes::from(chunk))))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.is_done()
     }
 }
 
@@ -16,7 +104,7 @@ impl<F: FnOnce(&mut Html)> IntoResponse for Fragment<F> {
     fn into_response(self) -> axum_core::response::Response {
         (
             [("Content-Type", "text/html; charset=utf-8")],
-            Html::from(self).to_string(),
+            self.into_response_html(),
         )
             .into_response()
     }
@@ -0,0 +1,113 @@
+//! [`Declarations`], typed CSS declarations.
+use std::fmt::{Display, Write};
+
+use forr::forr;
+use html_escape::encode_style;
+
+use crate::attributes::{Any, Style, ToAttribute};
+use crate::{Html, ToStyle};
+
+/// An ordered list of CSS declarations, i.e., `property:value` pairs.
+///
+/// A typed alternative to CSS strings for simple cases, it can be used as an
+/// inline `style` attribute, or inside a rule in a `<style>` element.
+/// Property names are passed through unchanged, e.g., `background-color` or
+/// custom properties like `--accent`.
+///
+/// ```
+/// # use htmx::{html, Declarations};
+/// let theme = Declarations::new()
+///     .set("--accent", "rebeccapurple")
+///     .set("color", "var(--accent)");
+/// assert_eq!(
+///     html! {
+///         <style>{(":root{", &theme, "}")}</style>
+///         <my-card style=&theme/>
+///     }
+///     .into_fragment()
+///     .to_string(),
+///     r#"<style>:root{--accent:rebeccapurple;color:var(--accent);}</style><my-card style="--accent:rebeccapurple;color:var(--accent)"></my-card>"#
+/// );
+/// ```
+#[must_use]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Declarations(Vec<(String, String)>);
+
+impl Declarations {
+    /// Creates an empty list of declarations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `property` to `value`, replacing an earlier value of `property`
+    /// in place.
+    pub fn set(mut self, property: impl Display, value: impl Display) -> Self {
+        let property = property.to_string();
+        let value = value.to_string();
+        if let Some(existing) = self.0.iter_mut().find(|(name, _)| *name == property) {
+            existing.1 = value;
+        } else {
+            self.0.push((property, value));
+        }
+        self
+    }
+
+    /// Returns whether no declaration was set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the declarations in the order they were set.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(property, value)| (property.as_str(), value.as_str()))
+    }
+}
+
+impl<P: Display, V: Display> FromIterator<(P, V)> for Declarations {
+    fn from_iter<T: IntoIterator<Item = (P, V)>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |declarations, (property, value)| {
+                declarations.set(property, value)
+            })
+    }
+}
+
+impl Display for Declarations {
+    /// Writes the declarations joined by `;`, without escaping.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (property, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_char(';')?;
+            }
+            write!(f, "{property}:{value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ToStyle for Declarations {
+    fn to_style(&self, out: &mut Html) {
+        for (property, value) in &self.0 {
+            write!(out, "{}:{};", encode_style(property), encode_style(value));
+        }
+    }
+}
+
+forr! { #marker:ty in [Any, Style] #*
+    impl ToAttribute<#marker> for Declarations {
+        fn write(&self, html: &mut Html) {
+            html.write_attr_value_encoded(self);
+        }
+
+        fn write_inner(&self, html: &mut Html) {
+            html.write_attr_value_inner_encoded(self);
+        }
+
+        fn is_unset(&self) -> bool {
+            self.is_empty()
+        }
+    }
+}
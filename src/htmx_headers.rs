@@ -0,0 +1,292 @@
+//! Typed htmx [request headers](https://htmx.org/reference/#request_headers),
+//! extracted as [`HtmxRequest`], and
+//! [response headers](https://htmx.org/reference/#response_headers), set using
+//! [`HtmlResponse::hx`].
+use std::fmt::{Display, Write};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::attributes::Swap;
+use crate::HtmlResponse;
+
+/// An htmx response header, see [`HtmlResponse::hx`].
+pub trait HxHeader {
+    /// Returns the header's name, e.g., `HX-Redirect`.
+    fn name(&self) -> &'static str;
+    /// Returns the header's value.
+    fn value(&self) -> String;
+}
+
+impl HtmlResponse {
+    /// Adds an htmx response header.
+    ///
+    /// ```
+    /// # use htmx::{html, HtmlResponse};
+    /// use htmx::attributes::Swap;
+    /// use htmx::htmx_headers::{HxReswap, HxRetarget, HxTrigger};
+    ///
+    /// let response = HtmlResponse::new(html! { <p>"Saved"</p> }.into_fragment())
+    ///     .hx(HxTrigger::new("saved").event("close-dialog"))
+    ///     .hx(HxRetarget("#status"))
+    ///     .hx(HxReswap(Swap::OuterHtml));
+    /// assert_eq!(response.headers().collect::<Vec<_>>(), [
+    ///     ("HX-Trigger", "saved, close-dialog"),
+    ///     ("HX-Retarget", "#status"),
+    ///     ("HX-Reswap", "outerHTML"),
+    /// ]);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn hx(self, header: impl HxHeader) -> Self {
+        self.header(header.name(), header.value())
+    }
+}
+
+/// Triggers client side events, using
+/// [`HX-Trigger`](https://htmx.org/headers/hx-trigger/).
+///
+/// Events without details are sent as a comma separated list, if any event
+/// has a detail, or a name with a `,` or non visible ASCII characters, all are
+/// sent as a JSON object:
+///
+/// ```
+/// # use htmx::htmx_headers::{HxHeader, HxTrigger};
+/// let trigger = HxTrigger::new("saved").detail("notify", "Item saved");
+/// assert_eq!(trigger.value(), r#"{"saved":null,"notify":"Item saved"}"#);
+/// assert_eq!(trigger.after_settle().name(), "HX-Trigger-After-Settle");
+/// ```
+#[must_use]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HxTrigger {
+    name: &'static str,
+    events: Vec<(String, Option<Value>)>,
+}
+
+impl HxTrigger {
+    /// Triggers `event` as soon as the response is received.
+    pub fn new(event: impl Display) -> Self {
+        Self {
+            name: "HX-Trigger",
+            events: Vec::new(),
+        }
+        .event(event)
+    }
+
+    /// Triggers `event` as well.
+    pub fn event(mut self, event: impl Display) -> Self {
+        self.events.push((event.to_string(), None));
+        self
+    }
+
+    /// Triggers `event` as well, passing `detail` to the event handler.
+    ///
+    /// # Panics
+    /// Panics if `detail` cannot be serialized as JSON, e.g., a map with
+    /// non-string keys.
+    pub fn detail(mut self, event: impl Display, detail: impl Serialize) -> Self {
+        let detail = serde_json::to_value(detail).expect("detail should be serializable as JSON");
+        self.events.push((event.to_string(), Some(detail)));
+        self
+    }
+
+    /// Triggers the events after the swap, using `HX-Trigger-After-Swap`.
+    pub fn after_swap(mut self) -> Self {
+        self.name = "HX-Trigger-After-Swap";
+        self
+    }
+
+    /// Triggers the events after the settle step, using
+    /// `HX-Trigger-After-Settle`.
+    pub fn after_settle(mut self) -> Self {
+        self.name = "HX-Trigger-After-Settle";
+        self
+    }
+}
+
+impl HxHeader for HxTrigger {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn value(&self) -> String {
+        if self.events.iter().all(|(event, detail)| {
+            detail.is_none() && !event.contains(|c| c == ',' || !matches!(c, ' '..='~'))
+        }) {
+            return self
+                .events
+                .iter()
+                .map(|(event, _)| event.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+        }
+        let mut value = String::from("{");
+        for (index, (event, detail)) in self.events.iter().enumerate() {
+            if index > 0 {
+                value.push(',');
+            }
+            value.push_str(&Value::from(event.as_str()).to_string());
+            value.push(':');
+            value.push_str(&detail.as_ref().unwrap_or(&Value::Null).to_string());
+        }
+        value.push('}');
+        ascii_json(&value)
+    }
+}
+
+/// Escapes non ASCII characters, as header values only allow ASCII. In JSON,
+/// these can only occur in strings, where `\u` escapes are valid.
+fn ascii_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        if c.is_ascii() {
+            escaped.push(c);
+        } else {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(escaped, "\\u{unit:04x}").expect("writing to a String does not fail");
+            }
+        }
+    }
+    escaped
+}
+
+/// Redirects to a new page with a full reload, using
+/// [`HX-Redirect`](https://htmx.org/headers/hx-redirect/).
+///
+/// Characters not allowed in header values, e.g., `ü`, are percent-encoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HxRedirect<T>(pub T);
+
+impl<T: Display> HxHeader for HxRedirect<T> {
+    fn name(&self) -> &'static str {
+        "HX-Redirect"
+    }
+
+    fn value(&self) -> String {
+        let mut encoded = String::new();
+        for c in self.0.to_string().chars() {
+            if c.is_ascii_graphic() {
+                encoded.push(c);
+            } else {
+                for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                    write!(encoded, "%{byte:02X}").expect("writing to a String does not fail");
+                }
+            }
+        }
+        encoded
+    }
+}
+
+/// Does a full refresh of the page, using `HX-Refresh`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HxRefresh;
+
+impl HxHeader for HxRefresh {
+    fn name(&self) -> &'static str {
+        "HX-Refresh"
+    }
+
+    fn value(&self) -> String {
+        "true".into()
+    }
+}
+
+/// Overrides the element's [`hx-swap`](https://htmx.org/attributes/hx-swap/),
+/// using `HX-Reswap`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HxReswap(pub Swap);
+
+impl HxHeader for HxReswap {
+    fn name(&self) -> &'static str {
+        "HX-Reswap"
+    }
+
+    fn value(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Overrides the element's
+/// [`hx-target`](https://htmx.org/attributes/hx-target/) with a CSS
+/// selector, using `HX-Retarget`, e.g., the [`selector`](crate::Id::selector)
+/// of an [`Id`](crate::Id).
+///
+/// Characters not allowed in header values, e.g., `ü`, are escaped like in
+/// CSS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HxRetarget<T>(pub T);
+
+impl<T: Display> HxHeader for HxRetarget<T> {
+    fn name(&self) -> &'static str {
+        "HX-Retarget"
+    }
+
+    fn value(&self) -> String {
+        let mut escaped = String::new();
+        for c in self.0.to_string().chars() {
+            if matches!(c, ' '..='~') {
+                escaped.push(c);
+            } else {
+                write!(escaped, "\\{:x} ", u32::from(c))
+                    .expect("writing to a String does not fail");
+            }
+        }
+        escaped
+    }
+}
+
+/// The htmx [request headers](https://htmx.org/reference/#request_headers),
+/// can be extracted in axum and actix handlers.
+///
+/// Missing headers result in `false` or `None`, i.e., requests not sent by
+/// htmx produce [`HtmxRequest::default()`].
+///
+/// ```
+/// # use htmx::{html, Html, HtmlPage};
+/// # use htmx::htmx_headers::HtmxRequest;
+/// async fn contacts(htmx: HtmxRequest) -> Html {
+///     let list = html! { <ul><li>"Ferris"</li></ul> };
+///     if htmx.is_partial() {
+///         list.into_fragment()
+///     } else {
+///         html! { <HtmlPage title="Contacts">{list}</HtmlPage> }.into()
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HtmxRequest {
+    /// The request was sent by htmx, `HX-Request`.
+    pub request: bool,
+    /// The request was sent by an element using
+    /// [`hx-boost`](https://htmx.org/attributes/hx-boost/), `HX-Boosted`.
+    pub boosted: bool,
+    /// The `id` of the triggered element, `HX-Trigger`.
+    pub trigger: Option<String>,
+    /// The `id` of the target element, `HX-Target`.
+    pub target: Option<String>,
+    /// The URL of the browser, `HX-Current-URL`.
+    pub current_url: Option<String>,
+}
+
+impl HtmxRequest {
+    /// Returns whether htmx swaps the response into the current page, i.e.,
+    /// a [`Fragment`](crate::Fragment) should be returned instead of a full
+    /// page.
+    ///
+    /// Boosted requests replace the whole body, so they expect a full page.
+    #[must_use]
+    pub fn is_partial(&self) -> bool {
+        self.request && !self.boosted
+    }
+
+    #[cfg(any(feature = "actix-web", feature = "axum"))]
+    pub(crate) fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Self {
+        Self {
+            request: header("HX-Request") == Some("true"),
+            boosted: header("HX-Boosted") == Some("true"),
+            trigger: header("HX-Trigger").map(Into::into),
+            target: header("HX-Target").map(Into::into),
+            current_url: header("HX-Current-URL").map(Into::into),
+        }
+    }
+}
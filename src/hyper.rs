@@ -0,0 +1,83 @@
+//! ```
+//! use htmx::html;
+//! use hyper::{Body, Response};
+//!
+//! let response: Response<Body> = html! { <h1>"Hello hyper"</h1> }.into();
+//! assert_eq!(
+//!     response.headers()["Content-Type"],
+//!     "text/html; charset=utf-8"
+//! );
+//! ```
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::Response;
+use hyper::Body;
+
+use crate::{Css, Fragment, Html, HtmlStream, HtmxSrc};
+
+fn response(content_type: &'static str, body: impl Into<Body>) -> Response<Body> {
+    let mut response = Response::new(body.into());
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+impl Stream for Html {
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Every flush boundary produces a separate chunk.
+        Poll::Ready(self.next_chunk().map(|chunk| Ok(Bytes::from(chunk))))
+    }
+}
+
+impl From<Html> for Body {
+    fn from(value: Html) -> Self {
+        Body::wrap_stream(value)
+    }
+}
+
+impl From<Html> for Response<Body> {
+    fn from(value: Html) -> Self {
+        response("text/html; charset=utf-8", value)
+    }
+}
+
+impl Stream for HtmlStream {
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // The next part is rendered when the previous chunk was sent.
+        Poll::Ready(self.get_mut().next().map(|chunk| Ok(Bytes::from(chunk))))
+    }
+}
+
+impl From<HtmlStream> for Response<Body> {
+    fn from(value: HtmlStream) -> Self {
+        response("text/html; charset=utf-8", Body::wrap_stream(value))
+    }
+}
+
+impl<F: FnOnce(&mut Html)> From<Fragment<F>> for Response<Body> {
+    fn from(value: Fragment<F>) -> Self {
+        value.into_response_html().into()
+    }
+}
+
+impl From<Css<'static>> for Response<Body> {
+    fn from(value: Css<'static>) -> Self {
+        response("text/css; charset=utf-8", value.0)
+    }
+}
+
+impl From<HtmxSrc> for Response<Body> {
+    fn from(_: HtmxSrc) -> Self {
+        response("text/javascript; charset=utf-8", HtmxSrc::HTMX_SRC)
+    }
+}
@@ -86,19 +86,38 @@
 extern crate self as htmx;
 
 use std::borrow::Cow;
-use std::fmt;
+use std::collections::VecDeque;
 use std::fmt::Write;
 use std::marker::PhantomData;
-use std::mem::ManuallyDrop;
+use std::mem::{self, ManuallyDrop};
+use std::{fmt, io};
 
-use attributes::{Any, ToAttribute};
+use attributes::{AttributeValue, Class, Style, Swap, Target, ToAttribute};
 use derive_more::{DerefMut, Display};
-use forr::forr;
+use forr::{forr, iff};
 use html_escape::encode_double_quoted_attribute;
 use serde::Serialize;
 
 pub mod attributes;
+mod declarations;
+pub use declarations::Declarations;
+#[macro_use]
 pub mod native;
+mod minify;
+mod placeholder;
+pub use placeholder::Placeholder;
+mod pretty;
+pub use pretty::PrettyHtml;
+mod response;
+pub use response::HtmlResponse;
+mod sink;
+pub use sink::IoWriter;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "hyper"))]
+mod stream;
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "hyper"))]
+pub use stream::HtmlStream;
+pub mod htmx_headers;
+pub mod svg;
 mod utils;
 pub use utils::*;
 
@@ -108,13 +127,57 @@ mod actix;
 #[cfg(feature = "axum")]
 mod axum;
 
+#[cfg(feature = "warp")]
+mod warp;
+
+#[cfg(feature = "rocket")]
+mod rocket;
+
+#[cfg(feature = "hyper")]
+mod hyper;
+
+#[cfg(feature = "sanitize")]
+mod sanitize;
+#[cfg(feature = "sanitize")]
+pub use sanitize::Sanitized;
+
 #[doc(hidden)]
 pub mod __private {
+    use std::fmt::Display;
+
+    use forr::forr;
+
+    use crate::attributes::{
+        BoolValue, Class, FlagOrAttributeValue, Number, Style, Swap, Target, ToAttribute, YesNo,
+    };
+    use crate::{Html, IntoHtml, ToHtml};
+
     pub trait Unused {
         fn unused() {}
     }
     impl<T> Unused for T {}
 
+    /// Allows `html!` to close merged `class` attributes on any element,
+    /// native elements in the `ClassesAttr` state shadow this with their
+    /// inherent `close_attr`.
+    pub trait CloseAttr: Sized {
+        #[must_use]
+        fn close_attr(self) -> Self {
+            self
+        }
+    }
+    impl<T> CloseAttr for T {}
+
+    /// Global attributes of native elements, forwarded by components with a
+    /// `tag` to that element, the component's props take precedence as
+    /// inherent methods.
+    pub trait ForwardAttrs: Sized {
+        #[must_use]
+        fn forward_attr(self, write: impl FnOnce(&mut Html)) -> Self;
+
+        global_attributes!(attributes!([@forward]));
+    }
+
     pub trait Settable<T> {
         fn get_or_default(self) -> T
         where
@@ -141,6 +204,78 @@ pub mod __private {
         }
     }
 
+    /// Attributes forwarded by components with a `tag`.
+    #[derive(Default)]
+    pub struct Attrs {
+        attrs: String,
+        /// Merged into a single `class` attribute.
+        classes: String,
+    }
+
+    impl Attrs {
+        pub fn push<Kind>(&mut self, key: impl Display, value: impl ToAttribute<Kind>) {
+            if !value.is_unset() {
+                self.write(|html| {
+                    html.write_attr_key(key);
+                    value.write(html);
+                });
+            }
+        }
+
+        pub fn write(&mut self, write: impl FnOnce(&mut Html)) {
+            let mut html = Html::empty();
+            write(&mut html);
+            self.attrs.push_str(&html.buf);
+        }
+
+        pub fn class(&mut self, value: impl ToAttribute<Class>) {
+            if !value.is_unset() {
+                let mut html = Html::empty();
+                value.write_inner(&mut html);
+                if !self.classes.is_empty() {
+                    self.classes.push(' ');
+                }
+                self.classes.push_str(&html.buf);
+            }
+        }
+    }
+
+    /// Renders `body` wrapped in the element `tag` with `attrs`.
+    pub fn tagged(html: &mut Html, tag: &str, attrs: &Attrs, body: impl IntoHtml) {
+        html.write_open_tag_unchecked(tag);
+        if !attrs.classes.is_empty() {
+            html.write_attr_key("class");
+            html.write_str("=\"");
+            html.write_str(&attrs.classes);
+            html.write_quote();
+        }
+        html.write_str(&attrs.attrs);
+        html.write_gt();
+        body.into_html(html);
+        html.write_close_tag_unchecked(tag);
+    }
+
+    /// Generates the id for `ref=` in `html!`, `prefix` is the ref's name, the
+    /// counter makes it unique per element in a render.
+    pub fn ref_id(html: &mut Html, prefix: &str) -> String {
+        html.refs += 1;
+        format!("{prefix}-{}", html.refs)
+    }
+
+    /// Values accepted by `attr=?value` in `html!`.
+    #[diagnostic::on_unimplemented(
+        message = "`attr=?value` expects `bool` or `Option`, found `{Self}`",
+        label = "expected `bool` or `Option`"
+    )]
+    pub trait Conditional {}
+    impl Conditional for bool {}
+    impl<T> Conditional for Option<T> {}
+    impl<T: Conditional> Conditional for &T {}
+
+    pub fn conditional<T: Conditional>(value: T) -> T {
+        value
+    }
+
     pub struct Set<T>(pub T);
     impl<T> Settable<T> for Set<T> {
         fn get_or_default(self) -> T
@@ -150,6 +285,16 @@ pub mod __private {
             self.0
         }
     }
+    impl ToHtml for Unset {
+        fn to_html(&self, _: &mut Html) {}
+    }
+
+    impl<T: IntoHtml> IntoHtml for Set<T> {
+        fn into_html(self, html: &mut Html) {
+            self.0.into_html(html);
+        }
+    }
+
     impl<T: IntoIterator> IntoIterator for Set<T> {
         type IntoIter = T::IntoIter;
         type Item = T::Item;
@@ -214,7 +359,155 @@ pub mod __private {
 /// ```
 /// The [`#[component]`](component) macro on functions, generates the struct and
 /// [`Into`] implementation [above](#struct), making the two equivalent.
+///
+/// # Props
+/// Props are required, unless they are `bool`, `Option` or have a
+/// `#[default]`. Missing required props are compile errors:
+/// ```compile_fail,E0277
+/// # use htmx::{component, html};
+/// #[component]
+/// fn Greeting(name: String) {
+///     html! { "Hello " {name} }
+/// }
+///
+/// // error: required prop `name` not set on component `Greeting`
+/// html! { <Greeting/> };
+/// ```
+///
+/// By default, the setters for props accept any value implementing
+/// <code>[Into]<T></code>. As this can lead to "type annotations needed"
+/// errors, e.g., when passing `iter.collect()`, it can be disabled with
+/// `#[prop(into = false)]`.
+/// ```
+/// # use htmx::{component, html};
+/// #[component]
+/// fn List(#[prop(into = false)] items: Vec<String>) {
+///     html! {
+///         <ul>
+///             for item in items {
+///                 <li>{item}</li>
+///             }
+///         </ul>
+///     }
+/// }
+///
+/// html! {
+///     <List items=["a", "b"].into_iter().map(String::from).collect()/>
+/// };
+/// ```
+///
+/// Components can be generic, props using the component's type parameters
+/// default to `#[prop(into = false)]`, to allow inferring them.
+///
+/// Besides `body`, arguments marked with `#[slot]` accept content as well,
+/// they are optional and can borrow like `body`.
+/// ```
+/// # use htmx::{component, html, IntoHtml};
+/// #[component]
+/// fn Layout(#[slot] header: impl IntoHtml, body: impl IntoHtml) {
+///     html! {
+///         <header>{header}</header>
+///         <main>{body}</main>
+///     }
+/// }
+///
+/// let title = "Title";
+/// html! {
+///     <Layout header=html! {<h1>{title}</h1>}>
+///         "Content"
+///     </Layout>
+/// };
+/// ```
+///
+/// Instead of `()`, components can return `Result<(), E>`, allowing to use
+/// `?`. The body then needs to return `Ok(html! {...})`, an error implementing
+/// [`IntoHtml`] is rendered in place of the component.
+/// ```
+/// # use htmx::{component, html};
+/// #[component]
+/// fn Count(value: String) -> Result<(), String> {
+///     let value: u32 = value.parse().map_err(|_| "invalid count".to_owned())?;
+///     Ok(html! {
+///         <output>{value}</output>
+///     })
+/// }
+///
+/// html! {
+///     <Count value="1"/>
+/// };
+/// ```
+///
+/// Setters are `pub` by default, this can be changed with
+/// `#[prop(vis = "pub(crate)")]`. Doc comments and `#[doc(hidden)]` on props
+/// are applied to their setters.
+///
+/// `#[prop(name = "type")]` sets the name used in [`html!`], allowing props
+/// named like keywords, e.g., `type` or `for`.
+/// ```
+/// # use htmx::{component, html};
+/// #[component]
+/// fn Field(#[prop(name = "type")] kind: String, name: String) {
+///     html! {
+///         <input type=kind name=name/>
+///     }
+/// }
+///
+/// html! {
+///     <Field type="email" name="mail"/>
+/// };
+/// ```
+///
+/// # Tag
+/// With `#[component(tag = "...")]` the output is wrapped in that element.
+/// Attributes that are not props, e.g., `data-id`, are set on the element.
+/// ```
+/// # use htmx::{component, html, Html, IntoHtml};
+/// #[component(tag = "section")]
+/// fn Section(title: String, body: impl IntoHtml) {
+///     html! {
+///         <h2>{title}</h2>
+///         {body}
+///     }
+/// }
+///
+/// html! {
+///     <Section title="Title" data-id="main">
+///         <p>"Content"</p>
+///     </_>
+/// };
+/// ```
 pub use htmx_macros::component;
+/// CSS using a Rust token based syntax, creating [`Css`].
+///
+/// Rust expressions in braces are interpolated into values using their
+/// [`Display`] implementation, they are inserted without any escaping.
+///
+/// ```
+/// # use htmx::css;
+/// let width = 80;
+/// let style = css! {
+///     .btn, a.active {
+///         background-color: #0af;
+///         padding: 0 1 em;
+///         width: calc({width}% - 2 em);
+///     }
+///     .card .title {
+///         font-family: "Helvetica Neue", sans-serif;
+///     }
+/// };
+/// # insta::assert_display_snapshot!("doc-css", style.0);
+/// ```
+///
+/// Numbers and interpolations followed by a unit are joined, e.g., `{size}px`
+/// becomes `12px`. As `1em` is not a valid Rust literal, write it as `1 em`.
+///
+/// As Rust's tokens contain no information about whitespace, some selectors
+/// are ambiguous. Classes, ids, pseudo classes and attributes following a
+/// tag name are part of the same compound selector, e.g., `a.active`, but
+/// following a class they are descendants, e.g., `.card .title`. Other
+/// selectors can be written as string literals, e.g., `".btn.primary"`, which
+/// are inserted verbatim.
+pub use htmx_macros::css;
 /// The `html!` macro allows constructing [`Html`] using an HTML like syntax.
 ///
 /// The native HTML elements in [`native`] are always available and do
@@ -228,20 +521,65 @@ pub use htmx_macros::component;
 /// will be created as [`CustomElement`]. This is also true when using blocks
 /// for tag names, e.g., `<{"tagname"}>`.
 ///
-/// To create an element, `html!` calls `TagName::builder()`, then it calls
+/// Native elements and components are not distinguished by the case of their
+/// name, both are resolved as rust paths. A lowercase component, e.g.,
+/// `use Card as card;`, is therefore treated like any other component, as long
+/// as it does not collide with a native element. To force a specific item, a
+/// full path can be used, e.g., `<self::card>`.
+///
+/// To create an element, `html!` calls `TagName::new(&mut html)`, then it calls
 /// `TagName::attribute_name(attribute_value)` for each
 /// `attribute_name=attribute_value`. When the attribute name is not a valid
 /// rust identifier, e.g., `attribute-name=...` or `{"string-name"}=...`, the
 /// macro tries to use `TagName::custom_attr("attribute-name", ...)`.
+/// Attribute names that are rust keywords, e.g., `type` or `for`, call the
+/// function suffixed with `_`, i.e., `TagName::type_(...)`.
 ///
 /// When the attribute starts with `hx::` and is a valid path, it will be
 /// translated from e.g., `hx::disabled_elt` to `hx-disabled-elt`.
+/// The same applies to `data::` paths and names starting with `data_`, e.g.,
+/// `data_user_id` sets `data-user-id`.
 /// To not accidentally mess up attributes i.e., when they are supposed to
 /// contain `::` or `_`, any other paths are not modified.
 ///
+/// Namespaced attributes, e.g., `xlink:href` or `xmlns:xlink`, are set
+/// verbatim, names using the `xml`, `xmlns` or `xlink` prefix are validated to
+/// be qualified names, i.e., contain a single `:`.
+///
+/// Attributes starting with `@` are translated to event handlers, e.g.,
+/// `@click="handler()"` sets `onclick="handler()"`.
+/// With `on:click={ console.log($value) }` the handler is written in the same
+/// Rust like JS syntax as `<script>` tags, see [`js!`].
+///
+/// `attr=?value` sets an attribute conditionally, `value` needs to be either a
+/// [`bool`] or an [`Option`], e.g., `disabled=?is_disabled` or
+/// `href=?maybe_url`. The value can be a path, field access, call or block.
+///
+/// `ref=name` sets a generated `id` on the element and binds it to `name`,
+/// allowing following nodes to reference the element, e.g., as `$name` in
+/// `<script>` tags. The id starts with `name` and is unique per element in a
+/// render, even in loops.
+///
 /// These are automatically generated when using the [`component`] attribute
 /// macro.
 ///
+/// Text is written as string literals, numeric and boolean literals are
+/// accepted as well, e.g., `<span>42</span>`.
+///
+/// A single block as the body of `<style>` is written as CSS, i.e., it needs
+/// to implement [`ToStyle`], so [`Html`] and the output of `html!` cannot be
+/// used there.
+///
+/// Control flow uses rust syntax, `if`, `for`, `while` and `loop` take a body
+/// of nodes, which can contain `break` and `continue`. As the body of an
+/// element is a closure, they cannot be used inside an element in the loop,
+/// e.g., `for x in xs { <p> if x.hidden { continue; } </p> }` is rejected,
+/// move the `if` around the element instead.
+/// `let PAT = EXPR else { ... };` binds `PAT` for the following nodes. If it
+/// does not match, the else branch is rendered instead of them, i.e., the
+/// rest of the surrounding body, e.g., of the element or loop iteration, is
+/// skipped.
+///
 /// ```
 /// # use htmx::html;
 /// let link = "example.com";
@@ -274,10 +612,34 @@ pub use htmx_macros::component;
 /// # );
 /// ```
 pub use htmx_macros::html;
+/// Creates a [`Script`] using the same Rust like JS syntax supported in
+/// `<script>` tags in [`html!`].
+///
+/// Rust values can be embedded with `$ident`, they are converted using
+/// [`ToJs`].
+///
+/// ```
+/// # use htmx::{html, js, RawSrc};
+/// let name = "World";
+/// let greet = js! {
+///     console.log($name);
+/// };
+/// # insta::assert_display_snapshot!("doc-js",
+/// html! {
+///     <script>{(greet, RawSrc::new("console.log(1);"))}</script>
+/// }
+/// .into_string()
+/// # );
+/// ```
+pub use htmx_macros::js;
 // TODO docs
 pub use htmx_macros::rtml;
 
 const DOCTYPE: &str = "<!DOCTYPE html>";
+const AMP_DOCTYPE: &str = "<!doctype html>";
+const AMP_BOILERPLATE: &str = "body{-webkit-animation:-amp-start 8s steps(1,end) 0s 1 normal both;-moz-animation:-amp-start 8s steps(1,end) 0s 1 normal both;-ms-animation:-amp-start 8s steps(1,end) 0s 1 normal both;animation:-amp-start 8s steps(1,end) 0s 1 normal both}@-webkit-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}@-moz-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}@-ms-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}@-o-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}@keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}";
+const AMP_NOSCRIPT_BOILERPLATE: &str =
+    "body{-webkit-animation:none;-moz-animation:none;-ms-animation:none;animation:none}";
 
 /// Trait used with the custom Rust like JS in `<script>` tags using the
 /// [`html!`] macro.
@@ -314,66 +676,461 @@ impl<T: Serialize> ToJs for T {
 /// HTML
 ///
 /// Can be returned from HTTP endpoints or converted to a string.
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Display)]
+///
+/// [`Display`] outputs the exact markup, while [`Debug`](fmt::Debug) puts
+/// every tag on its own line to keep test failures readable.
+///
+/// Boundaries marked with [`Html::flush`] split the body into separate chunks
+/// when returned from an endpoint.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[must_use]
-pub struct Html(String);
+pub struct Html {
+    buf: String,
+    /// Offsets marked using [`Html::flush`].
+    boundaries: VecDeque<usize>,
+    /// Unfilled [`Placeholder`]s.
+    holes: Vec<placeholder::Hole>,
+    /// Set using [`Html::with_nonce`].
+    nonce: Option<String>,
+    /// Number of ids generated for `ref=` attributes.
+    refs: usize,
+    /// Set using [`Html::with_sink`].
+    sink: sink::Sink,
+    /// Key of the `class` or `style` attribute opened by [`ClassesAttr`] or
+    /// [`StyleAttr`], it is only written with the first value.
+    pending_attr: Option<&'static str>,
+}
+
+impl Display for Html {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+impl fmt::Debug for Html {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Html(\n    ")?;
+        let mut chars = self.buf.chars().peekable();
+        while let Some(c) = chars.next() {
+            f.write_char(c)?;
+            match (c, chars.peek()) {
+                (_, None) => {}
+                ('>', _) | (_, Some('<')) => f.write_str("\n    ")?,
+                _ => {}
+            }
+        }
+        f.write_str("\n)")
+    }
+}
+
+/// Embeds already rendered HTML, e.g., a cached section of a page.
+///
+/// The leading doctype is stripped, so it does not end up in the middle of
+/// the document, [flush boundaries](Html::flush) are kept.
+///
+/// ```
+/// # use htmx::{html, Html};
+/// let nav = Html::from(html! { <nav>"Home"</nav> });
+/// assert_eq!(
+///     Html::from(html! { <body>{&nav}</body> }).to_string(),
+///     "<!DOCTYPE html><body><nav>Home</nav></body>"
+/// );
+/// ```
+impl ToHtml for Html {
+    fn to_html(&self, html: &mut Html) {
+        let content = self.body_only();
+        let stripped = self.buf.len() - content.len();
+        let start = html.buf.len();
+        html.write_str(content);
+        // A sink already received the content.
+        if html.sink.is_set() {
+            return;
+        }
+        html.boundaries.extend(
+            self.boundaries
+                .iter()
+                .filter(|&&boundary| boundary > stripped)
+                .map(|boundary| start + boundary - stripped),
+        );
+        html.holes.extend(
+            self.holes
+                .iter()
+                .map(|hole| hole.moved_to(start + hole.offset - stripped)),
+        );
+    }
+}
 
 impl Html {
+    // Go through `WriteHtml`, so tags and attributes reach the sink.
+    forr! { $method:ident in [write_quote, write_gt] $*
+        fn $method(&mut self) {
+            WriteHtml::$method(self);
+        }
+    }
+
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded] $*
+        fn $method(&mut self, value: impl Display) {
+            WriteHtml::$method(self, value);
+        }
+    }
+
+    /// Creates an empty piece of HTML, all constructors start from this.
+    const fn empty() -> Self {
+        Self {
+            buf: String::new(),
+            boundaries: VecDeque::new(),
+            holes: Vec::new(),
+            nonce: None,
+            refs: 0,
+            sink: sink::Sink::none(),
+            pending_attr: None,
+        }
+    }
+
     fn write_str(&mut self, s: &str) {
-        self.0.push_str(s);
+        match self.sink.get() {
+            Some(sink) => sink.write_str(s),
+            None => self.buf.push_str(s),
+        }
     }
 
     fn write_char(&mut self, c: char) {
-        self.0.push(c);
+        match self.sink.get() {
+            Some(sink) => sink.write_char(c),
+            None => self.buf.push(c),
+        }
     }
 
     fn write_fmt(&mut self, a: fmt::Arguments) {
-        self.0.write_fmt(a).unwrap();
+        match self.sink.get() {
+            Some(sink) => sink.write_fmt(a),
+            None => self.buf.write_fmt(a).unwrap(),
+        }
     }
 
-    fn write_quote(&mut self) {
-        self.write_char('"');
+    /// Whether nothing was written yet, e.g., to only write the doctype at
+    /// the start.
+    fn nothing_written(&self) -> bool {
+        self.buf.is_empty() && !self.sink.written()
     }
 
-    fn write_gt(&mut self) {
-        self.write_char('>');
+    /// Writes the key of the attribute opened by [`ClassesAttr`] or
+    /// [`StyleAttr`] before its first value, and `separator` before the
+    /// following ones.
+    fn start_merged_value(&mut self, separator: &str) {
+        match self.pending_attr.take() {
+            Some(key) => {
+                self.write_attr_key(key);
+                self.write_str("=\"");
+            }
+            None => self.write_str(separator),
+        }
     }
 
-    fn write_open_tag_unchecked(&mut self, name: impl Display) {
-        debug_assert!(name.to_string().to_ascii_lowercase().chars().all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')),
-         "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
-        );
-        write!(self, "<{name}");
+    /// Closes the attribute opened by [`ClassesAttr`] or [`StyleAttr`], it
+    /// is omitted if no value was added.
+    fn close_merged_attr(&mut self) {
+        if self.pending_attr.take().is_none() {
+            self.write_quote();
+        }
+    }
+}
+
+impl Html {
+    /// Creates a piece of HTML, starting with the `<!DOCTYPE html>`.
+    ///
+    /// Use this for full pages, for partial responses, e.g., content swapped
+    /// in by htmx, use [`Html::fragment`].
+    pub fn new() -> Self {
+        Self {
+            buf: DOCTYPE.into(),
+            ..Self::empty()
+        }
     }
 
-    fn write_close_tag_unchecked(&mut self, name: impl Display) {
-        debug_assert!(name.to_string().to_ascii_lowercase().chars().all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')),
-         "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
+    /// Creates a piece of HTML, starting with the `<!DOCTYPE html>`, that adds
+    /// `nonce` to every `<script>` and `<style>` element.
+    ///
+    /// This allows inline scripts and styles under a
+    /// [Content-Security-Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src#unsafe_inline_script)
+    /// of `script-src 'nonce-{nonce}'`. The nonce needs to be random and
+    /// unique for each response.
+    ///
+    /// Only elements rendered into this `Html` get the nonce, [`Html`]
+    /// rendered separately and embedded afterwards does not.
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let mut html = Html::with_nonce("r4nd0m");
+    /// html! {
+    ///     <script>"console.log(1)"</script>
+    ///     <style>"p { color: red }"</style>
+    /// }
+    /// .into_html(&mut html);
+    /// assert_eq!(
+    ///     html.body_only(),
+    ///     r#"<script nonce="r4nd0m">console.log(1)</script><style nonce="r4nd0m">p { color: red }</style>"#
+    /// );
+    /// ```
+    pub fn with_nonce(nonce: impl Into<String>) -> Self {
+        Self {
+            buf: DOCTYPE.into(),
+            nonce: Some(nonce.into()),
+            ..Self::empty()
+        }
+    }
+
+    /// Returns the nonce set using [`Html::with_nonce`], e.g., to add it to
+    /// the `Content-Security-Policy` header.
+    #[must_use]
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// Writes the ` nonce` attribute, if this has a nonce.
+    fn write_nonce(&mut self) {
+        if let Some(nonce) = self.nonce.take() {
+            self.write_attr_key("nonce");
+            self.write_attr_value_encoded(&nonce);
+            self.nonce = Some(nonce);
+        }
+    }
+
+    /// Creates a piece of HTML, starting with `doctype` instead of
+    /// `<!DOCTYPE html>`, e.g., for XHTML.
+    ///
+    /// `doctype` is written verbatim, `None` omits the doctype, like
+    /// [`Html::fragment`].
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let doctype = r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.1//EN" "http://www.w3.org/TR/xhtml11/DTD/xhtml11.dtd">"#;
+    /// let mut html = Html::with_doctype(Some(doctype));
+    /// html! { <p>"Hello"</p> }.into_html(&mut html);
+    /// assert_eq!(html.to_string(), format!("{doctype}<p>Hello</p>"));
+    /// assert_eq!(html.body_only(), "<p>Hello</p>");
+    /// ```
+    pub fn with_doctype(doctype: Option<&str>) -> Self {
+        Self {
+            buf: doctype.unwrap_or_default().into(),
+            ..Self::empty()
+        }
+    }
+
+    /// Creates a piece of HTML without a doctype.
+    ///
+    /// Use this for partial responses, e.g., content swapped in by htmx,
+    /// where a doctype would end up in the middle of the document. Returning
+    /// a [`Fragment`], e.g., the output of [`html!`], from an endpoint uses
+    /// this, unless it renders a full page starting with `<html>`, while
+    /// [`HtmlPage`] adds the doctype when rendered into an empty fragment.
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let mut html = Html::fragment();
+    /// html! { <li>"Item"</li> }.into_html(&mut html);
+    /// assert_eq!(html.to_string(), "<li>Item</li>");
+    /// ```
+    pub fn fragment() -> Self {
+        Self::empty()
+    }
+
+    /// Creates a piece of HTML, preallocating space for at least `capacity`
+    /// bytes.
+    ///
+    /// Useful when rendering large pages, e.g., long tables, to avoid
+    /// repeatedly growing the buffer.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut buf = String::with_capacity(capacity.max(DOCTYPE.len()));
+        buf.push_str(DOCTYPE);
+        Self {
+            buf,
+            ..Self::empty()
+        }
+    }
+
+    /// Returns the HTML without the leading doctype, e.g., to compare
+    /// fragments in tests.
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let html = Html::from(html! { <p>"Hello"</p> });
+    /// assert_eq!(html.body_only(), "<p>Hello</p>");
+    /// ```
+    #[must_use]
+    pub fn body_only(&self) -> &str {
+        if self
+            .buf
+            .get(..9)
+            .is_some_and(|start| start.eq_ignore_ascii_case("<!doctype"))
+        {
+            if let Some(end) = self.buf.find('>') {
+                return &self.buf[end + 1..];
+            }
+        }
+        &self.buf
+    }
+
+    /// Reserves capacity for at least `additional` more bytes, see
+    /// [`String::reserve`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional);
+    }
+
+    /// Creates an [AMP](https://amp.dev/documentation/guides-and-tutorials/learn/spec/amphtml#required-markup)
+    /// page, containing the required `<html amp>` shell and boilerplate.
+    ///
+    /// `head` should contain at least the `<title>` and
+    /// `<link rel="canonical">`.
+    pub fn amp(head: impl IntoHtml, body: impl IntoHtml) -> Self {
+        let mut html = Self {
+            buf: AMP_DOCTYPE.into(),
+            ..Self::empty()
+        };
+        html! {
+            <html {"amp"}>
+                <head>
+                    <meta charset="utf-8"/>
+                    <script async_ src="https://cdn.ampproject.org/v0.js"/>
+                    <meta name="viewport" content="width=device-width"/>
+                    {head}
+                    <style amp-boilerplate>{RawSrc::new(AMP_BOILERPLATE)}</style>
+                    <noscript>
+                        <style amp-boilerplate>{RawSrc::new(AMP_NOSCRIPT_BOILERPLATE)}</style>
+                    </noscript>
+                </head>
+                <body>
+                    {body}
+                </body>
+            </html>
+        }
+        .into_html(&mut html);
+        html
+    }
+
+    /// Creates HTML for emails, i.e., without a doctype as some email clients
+    /// do not handle it.
+    pub fn email(body: impl IntoHtml) -> Self {
+        let mut html = Self::fragment();
+        body.into_html(&mut html);
+        html
+    }
+
+    /// Runs `transform` over the rendered HTML, e.g., to rewrite asset URLs or
+    /// inject analytics.
+    ///
+    /// `transform` operates on the raw output, so it needs to ensure it only
+    /// produces valid and properly escaped HTML.
+    ///
+    /// [Flush boundaries](Self::flush) are kept if `transform` does not
+    /// change the length of the output, otherwise they are removed as their
+    /// offsets could be anywhere in the new output.
+    ///
+    /// # Panics
+    /// [Placeholders](Placeholder) need to be filled before, unfilled ones
+    /// panic in debug builds and are removed otherwise.
+    pub fn post_process(mut self, transform: impl FnOnce(&mut String)) -> Self {
+        debug_assert!(
+            self.holes.is_empty(),
+            "placeholders need to be filled before `post_process`"
         );
-        write!(self, "</{name}>");
+        let len = self.buf.len();
+        transform(&mut self.buf);
+        if self.buf.len() != len {
+            self.boundaries.clear();
+        }
+        self.holes.clear();
+        self
     }
 
-    fn write_attr_value_unchecked(&mut self, value: impl Display) {
-        write!(self, "=\"{value}\"");
+    /// Marks a flush boundary, when returned from an endpoint, everything
+    /// written before it is sent as its own chunk.
+    ///
+    /// The chunks are only split off after the HTML is fully rendered, i.e.,
+    /// the first chunk is not sent any earlier than a body without
+    /// boundaries. To send content before the rest is rendered, render it in
+    /// parts using an `HtmlStream`.
+    ///
+    /// In [`html!`] this can be done using [`Flush`].
+    pub fn flush(&mut self) {
+        if !self.buf.is_empty() && self.boundaries.back() != Some(&self.buf.len()) {
+            self.boundaries.push_back(self.buf.len());
+        }
     }
 
-    fn write_attr_value_inner_unchecked(&mut self, value: impl Display) {
-        write!(self, "{value}");
+    /// Splits the HTML into the chunks separated by
+    /// [flush boundaries](Self::flush).
+    ///
+    /// ```
+    /// # use htmx::{html, Flush, Html};
+    /// let html = Html::from(html! {
+    ///     <head><title>"Page"</title></head>
+    ///     <Flush/>
+    ///     <body>"slow content"</body>
+    /// });
+    /// assert_eq!(html.split_at_boundary(), [
+    ///     "<!DOCTYPE html><head><title>Page</title></head>",
+    ///     "<body>slow content</body>"
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn split_at_boundary(mut self) -> Vec<String> {
+        let mut chunks = Vec::with_capacity(self.boundaries.len() + 1);
+        while let Some(chunk) = self.next_chunk() {
+            chunks.push(chunk);
+        }
+        chunks
     }
 
-    fn write_attr_value_encoded(&mut self, value: impl Display) {
-        self.write_attr_value_unchecked(encode_double_quoted_attribute(&value.to_string()));
+    fn next_chunk(&mut self) -> Option<String> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        let Some(end) = self.boundaries.pop_front() else {
+            return Some(mem::take(&mut self.buf));
+        };
+        let rest = self.buf.split_off(end);
+        for boundary in &mut self.boundaries {
+            *boundary -= end;
+        }
+        Some(mem::replace(&mut self.buf, rest))
     }
 
-    fn write_attr_value_inner_encoded(&mut self, value: impl Display) {
-        self.write_attr_value_inner_unchecked(encode_double_quoted_attribute(&value.to_string()));
+    /// Returns the rendered HTML, without copying it.
+    ///
+    /// This drops all [flush boundaries](Self::flush).
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.buf
     }
-}
 
-impl Html {
-    /// Creates a piece of HTML.
-    pub fn new() -> Self {
-        Self(DOCTYPE.into())
+    /// Returns the rendered HTML.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Returns the length of the rendered HTML in bytes, including the
+    /// doctype, e.g., to set `Content-Length`.
+    ///
+    /// A [`Fragment`] needs to be rendered first to know its length, e.g.,
+    /// using [`Fragment::into_fragment`].
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let html = html! { <p>"Hello"</p> }.into_fragment();
+    /// assert_eq!(html.len(), "<p>Hello</p>".len());
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns whether nothing was rendered, this is never the case for
+    /// [`Html::new`] as it contains the doctype.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
     }
 
     pub fn child_expr(mut self, child: impl ToHtml) -> Self {
@@ -390,9 +1147,106 @@ impl Default for Html {
     fn default() -> Self {
         Self::new()
     }
-}
+}
+
+impl WriteHtml for Html {
+    // Tags and attributes are passed to the sink, if there is one, otherwise
+    // written into the buffer by the default implementations.
+    forr! { $method:ident in [write_quote, write_gt] $*
+        fn $method(&mut self) {
+            match self.sink.get() {
+                Some(sink) => sink.$method(),
+                None => Buffer(&mut self.buf).$method(),
+            }
+        }
+    }
+
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded, write_attr_value_minified] $*
+        fn $method(&mut self, value: impl Display) {
+            match self.sink.get() {
+                Some(sink) => sink.$method(&value),
+                None => Buffer(&mut self.buf).$method(value),
+            }
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        Html::write_str(self, s);
+    }
+
+    fn write_char(&mut self, c: char) {
+        Html::write_char(self, c);
+    }
+
+    fn write_fmt(&mut self, a: fmt::Arguments) {
+        Html::write_fmt(self, a);
+    }
+}
+
+/// The buffer of an [`Html`] without a sink.
+struct Buffer<'a>(&'a mut String);
+
+impl WriteHtml for Buffer<'_> {
+    fn write_str(&mut self, s: &str) {
+        self.0.push_str(s);
+    }
+
+    fn write_char(&mut self, c: char) {
+        self.0.push(c);
+    }
+
+    fn write_fmt(&mut self, a: fmt::Arguments) {
+        self.0.write_fmt(a).unwrap();
+    }
+}
+
+/// Adapter implementing [`fmt::Write`] that HTML-escapes everything written
+/// to it, see [`write_escaped!`].
+pub struct Escaped<W>(pub W);
+
+impl<W: WriteHtml> Write for Escaped<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(&html_escape::encode_text(s));
+        Ok(())
+    }
+}
+
+/// Formats and HTML-escapes into an [`Html`] or [`WriteHtml`], without
+/// allocating a temporary [`String`].
+///
+/// ```
+/// # use htmx::{write_escaped, Html};
+/// let mut html = Html::new();
+/// write_escaped!(html, "<b>{}</b>", "Tom & Jerry");
+/// assert_eq!(
+///     html.to_string(),
+///     "<!DOCTYPE html>&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;"
+/// );
+/// ```
+#[macro_export]
+macro_rules! write_escaped {
+    ($html:expr, $($fmt:tt)*) => {
+        ::core::fmt::Write::write_fmt(
+            &mut $crate::Escaped(&mut $html),
+            ::core::format_args!($($fmt)*),
+        )
+        .expect("writing html should not fail")
+    };
+}
+
+impl<T: WriteHtml + ?Sized> WriteHtml for &mut T {
+    forr! { $method:ident in [write_quote, write_gt] $*
+        fn $method(&mut self) {
+            T::$method(self);
+        }
+    }
+
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded, write_attr_value_minified] $*
+        fn $method(&mut self, value: impl Display) {
+            T::$method(self, value);
+        }
+    }
 
-impl<T: WriteHtml + ?Sized> WriteHtml for &mut T {
     fn write_str(&mut self, s: &str) {
         T::write_str(self, s);
     }
@@ -406,7 +1260,34 @@ impl<T: WriteHtml + ?Sized> WriteHtml for &mut T {
     }
 }
 
+/// Implements [`WriteHtml`] by forwarding to a buffer field, e.g., to wrap
+/// [`Html`] with additional context.
+///
+/// The buffer is the field marked with `#[html]`, or the first field if none
+/// is marked.
+///
+/// ```
+/// # use htmx::{Html, WriteHtml};
+/// #[derive(WriteHtml)]
+/// struct Logged {
+///     name: &'static str,
+///     #[html]
+///     html: Html,
+/// }
+///
+/// let mut logged = Logged {
+///     name: "page",
+///     html: Html::fragment(),
+/// };
+/// logged.write_str("<p>");
+/// assert_eq!(logged.html.to_string(), "<p>");
+/// ```
 pub use htmx_macros::WriteHtml;
+/// Writing HTML, implemented by [`Html`].
+///
+/// Elements write their tags and attributes through the granular methods,
+/// an implementation passed to [`Html::with_sink`] can override them, e.g.,
+/// to observe or change the written attributes.
 pub trait WriteHtml {
     fn write_str(&mut self, s: &str);
 
@@ -420,6 +1301,13 @@ pub trait WriteHtml {
         self.write_char('>');
     }
 
+    /// Writes an attribute's key, preceded by a space, the value is written
+    /// afterwards, e.g., using
+    /// [`write_attr_value_encoded`](Self::write_attr_value_encoded).
+    fn write_attr_key(&mut self, key: impl Display) {
+        write!(self, " {key}");
+    }
+
     fn write_open_tag_unchecked(&mut self, name: impl Display) {
         debug_assert!(name.to_string().to_ascii_lowercase().chars().all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')),
          "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
@@ -450,10 +1338,59 @@ pub trait WriteHtml {
         self.write_attr_value_inner_unchecked(encode_double_quoted_attribute(&value.to_string()));
     }
 
+    /// Writes the attribute value without quotes if that is
+    /// [allowed](https://html.spec.whatwg.org/multipage/syntax.html#unquoted), i.e., it is
+    /// not empty and contains neither whitespace nor any of ``"'=<>`&``,
+    /// otherwise like
+    /// [`write_attr_value_encoded`](Self::write_attr_value_encoded).
+    ///
+    /// ```
+    /// # use htmx::{Html, WriteHtml};
+    /// let mut html = Html::fragment();
+    /// html.write_str("<input value");
+    /// WriteHtml::write_attr_value_minified(&mut html, "");
+    /// html.write_str(" name");
+    /// WriteHtml::write_attr_value_minified(&mut html, "q");
+    /// html.write_str(" placeholder");
+    /// WriteHtml::write_attr_value_minified(&mut html, "Search here");
+    /// html.write_str(">");
+    /// assert_eq!(
+    ///     html.to_string(),
+    ///     r#"<input value="" name=q placeholder="Search here">"#
+    /// );
+    /// ```
+    fn write_attr_value_minified(&mut self, value: impl Display) {
+        let value = value.to_string();
+        if can_unquote(&value) {
+            write!(self, "={value}");
+        } else {
+            self.write_attr_value_encoded(value);
+        }
+    }
+
     fn write_fmt(&mut self, a: fmt::Arguments);
 }
 
+fn can_unquote(value: &str) -> bool {
+    !value.is_empty()
+        && !value.chars().any(|c| {
+            c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`' | '&')
+        })
+}
+
 impl<T: WriteHtml> WriteHtml for ManuallyDrop<T> {
+    forr! { $method:ident in [write_quote, write_gt] $*
+        fn $method(&mut self) {
+            self.deref_mut().$method();
+        }
+    }
+
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded, write_attr_value_minified] $*
+        fn $method(&mut self, value: impl Display) {
+            self.deref_mut().$method(value);
+        }
+    }
+
     fn write_str(&mut self, s: &str) {
         self.deref_mut().write_str(s);
     }
@@ -501,23 +1438,37 @@ impl<'html> CustomElement<'html, Tag> {
     /// existence of a `-`.
     pub fn new_unchecked(html: &'html mut Html, name: impl Into<Cow<'html, str>>) -> Self {
         let name = name.into();
-        debug_assert!(name.to_ascii_lowercase().chars().all(|c| matches!(c, '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')),
-         "invalid tag name `{name}`, https://html.spec.whatwg.org/multipage/custom-elements.html#prod-potentialcustomelementname"
-        );
-        write!(html, "<{name}");
+        html.write_open_tag_unchecked(&name);
         Self {
             html,
             name,
             state: PhantomData,
         }
     }
+}
+
+// Attributes can be set in any state with an open tag, pending `class` or
+// `style` attributes are closed first.
+impl<'html, S: AttrState> CustomElement<'html, S> {
+    forr! { $attr:ty in [hx_swap="hx-swap"<Swap>, hx_target="hx-target"<Target>] $*
+        attribute!([CustomElement<'html, Tag>] htmx|$attr);
+    }
+
+    fn into_tag(self) -> CustomElement<'html, Tag> {
+        S::close_attr(self.html);
+        self.change_state()
+    }
 
     /// Sets the attribute `key`, this does not do any type checking and allows
     /// [`IntoAttribute<Any>`].
     ///
     /// # Panics
     /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
-    pub fn custom_attr(self, key: impl Display, value: impl ToAttribute<Any>) -> Self {
+    pub fn custom_attr(
+        self,
+        key: impl Display,
+        value: impl AttributeValue,
+    ) -> CustomElement<'html, Tag> {
         assert!(!key.to_string().chars().any(|c| c.is_whitespace()
             || c.is_control()
             || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
@@ -528,13 +1479,43 @@ impl<'html> CustomElement<'html, Tag> {
     /// [`AnyAttributeValue`], without checking for invalid characters.
     ///
     /// Note: This function does contain the check for [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0) only in debug builds, failing to ensure valid keys can lead to broken HTML output.
-    pub fn custom_attr_unchecked(self, key: impl Display, value: impl ToAttribute<Any>) -> Self {
+    pub fn custom_attr_unchecked(
+        self,
+        key: impl Display,
+        value: impl AttributeValue,
+    ) -> CustomElement<'html, Tag> {
         debug_assert!(!key.to_string().chars().any(|c| c.is_whitespace()
             || c.is_control()
             || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
-        write!(self.html, " {key}");
-        value.write(self.html);
-        self
+        let this = self.into_tag();
+        if !value.is_unset() {
+            this.html.write_attr_key(key);
+            value.write(this.html);
+        }
+        this
+    }
+
+    /// Sets multiple attributes, see [`custom_attr`](Self::custom_attr).
+    ///
+    /// # Panics
+    /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+    pub fn custom_attrs<K: Display, V: AttributeValue>(
+        self,
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> CustomElement<'html, Tag> {
+        let mut this = self.into_tag();
+        for (key, value) in attrs {
+            this = this.custom_attr(key, value);
+        }
+        this
+    }
+
+    /// Sets the `data-{key}` attribute, e.g., to pass values to JS.
+    ///
+    /// # Panics
+    /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+    pub fn data(self, key: impl Display, value: impl AttributeValue) -> CustomElement<'html, Tag> {
+        self.custom_attr(format_args!("data-{key}"), value)
     }
 
     // TODO, use closure like body
@@ -555,9 +1536,94 @@ impl<'html> CustomElement<'html, Tag> {
     //     write!(self.html, " {key}=\"");
     //     self.change_state()
     // }
+}
+
+impl<'html> CustomElement<'html, Tag> {
+    /// Adds classes to the element.
+    ///
+    /// Can be called multiple times, all classes are merged into a single
+    /// `class` attribute, which is closed by setting any other attribute.
+    pub fn class(self, value: impl ToAttribute<Class>) -> CustomElement<'html, ClassesAttr> {
+        ClassesAttr::open(self.html);
+        ClassesAttr::add(self.html, value);
+        self.change_state()
+    }
+
+    /// Adds CSS declarations to the element, e.g., `"color: red"`.
+    ///
+    /// Can be called multiple times, all declarations are merged into a single
+    /// `style` attribute, which is closed by setting any other attribute.
+    ///
+    /// ```
+    /// # use htmx::{CustomElement, Html, IntoHtml};
+    /// let mut html = Html::new();
+    /// CustomElement::new(&mut html, "my-card")
+    ///     .class("card")
+    ///     .class("wide")
+    ///     .style("color: red")
+    ///     .style("margin: 0")
+    ///     .custom_attr("title", "Card")
+    ///     .close()
+    ///     .into_html(&mut html);
+    /// assert_eq!(
+    ///     html.body_only(),
+    ///     r#"<my-card class="card wide" style="color: red; margin: 0" title="Card"></my-card>"#
+    /// );
+    /// ```
+    pub fn style(self, value: impl ToAttribute<Style>) -> CustomElement<'html, StyleAttr> {
+        StyleAttr::open(self.html);
+        StyleAttr::add(self.html, value);
+        self.change_state()
+    }
+}
+
+impl<'html> CustomElement<'html, ClassesAttr> {
+    /// Adds classes to the element.
+    pub fn class(self, value: impl ToAttribute<Class>) -> Self {
+        ClassesAttr::add(self.html, value);
+        self
+    }
+
+    /// Closes the `class` attribute and adds CSS declarations to the element.
+    pub fn style(self, value: impl ToAttribute<Style>) -> CustomElement<'html, StyleAttr> {
+        self.close_attr().style(value)
+    }
+
+    /// Closes the `class` attribute, allowing to set other attributes.
+    pub fn close_attr(self) -> CustomElement<'html, Tag> {
+        self.into_tag()
+    }
+}
+
+impl<'html> CustomElement<'html, StyleAttr> {
+    /// Adds CSS declarations to the element.
+    pub fn style(self, value: impl ToAttribute<Style>) -> Self {
+        StyleAttr::add(self.html, value);
+        self
+    }
+
+    /// Closes the `style` attribute and adds classes to the element.
+    pub fn class(self, value: impl ToAttribute<Class>) -> CustomElement<'html, ClassesAttr> {
+        self.close_attr().class(value)
+    }
+
+    /// Closes the `style` attribute, allowing to set other attributes.
+    pub fn close_attr(self) -> CustomElement<'html, Tag> {
+        self.into_tag()
+    }
+}
+
+impl<'html, S: ElementState> CustomElement<'html, S> {
+    fn change_state<T: ElementState>(self) -> CustomElement<'html, T> {
+        CustomElement {
+            html: self.html,
+            name: self.name,
+            state: PhantomData,
+        }
+    }
 
     pub fn body(self, body: impl IntoHtml) -> impl IntoHtml {
-        Tag::close_tag(self.html);
+        S::close_tag(self.html);
         body.into_html(self.html);
         self.html.write_close_tag_unchecked(self.name.as_ref());
 
@@ -589,8 +1655,88 @@ impl<'a> RawSrc<'a> {
     }
 }
 
+/// Trusted HTML, put into the output without escaping.
+///
+/// Unlike [`RawSrc`] this is only usable as HTML, not in scripts or styles,
+/// making every place raw HTML is injected easy to find.
+///
+/// # Security
+/// The content must come from a trusted source, e.g., a template rendered
+/// by the server, never from user input, as this would allow
+/// [XSS](https://owasp.org/www-community/attacks/xss/).
+///
+/// ```
+/// # use htmx::{html, Trusted};
+/// let cached = "<nav>Home</nav>";
+/// assert_eq!(
+///     html! { <Trusted(cached)/> }.to_string(),
+///     "<!DOCTYPE html><nav>Home</nav>"
+/// );
+/// ```
+#[must_use = "`Trusted` should only be created where it is rendered"]
+pub struct Trusted<'a>(pub Cow<'a, str>);
+
+impl<'a> Trusted<'a> {
+    /// Marks `content` as trusted HTML.
+    pub fn new(content: impl Into<Cow<'a, str>>) -> Self {
+        Self(content.into())
+    }
+}
+
+/// An HTML comment, `<!-- ... -->`.
+///
+/// In [`html!`] comments can be written as `<!-- "text" -->`.
+///
+/// The content is adjusted to not end the comment early, i.e., a space is
+/// inserted into every `--`, and before a leading `>` or `-` and after a
+/// trailing `-`.
+///
+/// ```
+/// # use htmx::{html, Comment};
+/// assert_eq!(
+///     html! {
+///         <!-- "build 42" -->
+///         <Comment("a --> b")/>
+///     }
+///     .to_string(),
+///     "<!DOCTYPE html><!--build 42--><!--a - -> b-->"
+/// );
+/// ```
+pub struct Comment<'a>(pub Cow<'a, str>);
+
+impl<'a> Comment<'a> {
+    /// Creates a new `Comment`.
+    pub fn new(content: impl Into<Cow<'a, str>>) -> Self {
+        Self(content.into())
+    }
+}
+
+impl ToHtml for Comment<'_> {
+    fn to_html(&self, html: &mut Html) {
+        html.write_str("<!--");
+        if self.0.starts_with(['>', '-']) {
+            html.write_char(' ');
+        }
+        let mut prev = None;
+        for c in self.0.chars() {
+            if c == '-' && prev == Some('-') {
+                html.write_char(' ');
+            }
+            html.write_char(c);
+            prev = Some(c);
+        }
+        if prev == Some('-') {
+            html.write_char(' ');
+        }
+        html.write_str("-->");
+    }
+}
+
 pub struct Fragment<F>(pub F);
 
+/// A [`Fragment`] with a boxed closure, created by [`Fragment::boxed`].
+pub type BoxedFragment<'a> = Fragment<Box<dyn FnOnce(&mut Html) + 'a>>;
+
 impl Fragment<fn(&mut Html)> {
     pub const EMPTY: Self = Self(|_| {});
 }
@@ -604,13 +1750,136 @@ impl<F: FnOnce(&mut Html)> From<Fragment<F>> for Html {
 }
 
 impl<F: FnOnce(&mut Html)> Fragment<F> {
+    /// Renders into an [`Html`] without a doctype, see [`Html::fragment`].
+    pub fn into_fragment(self) -> Html {
+        let mut html = Html::fragment();
+        self.into_html(&mut html);
+        html
+    }
+
+    /// Renders for returning from an endpoint, like
+    /// [`into_fragment`](Self::into_fragment), but adds the doctype to full
+    /// pages, i.e., output starting with `<html`.
+    #[cfg_attr(
+        not(any(
+            feature = "actix-web",
+            feature = "axum",
+            feature = "warp",
+            feature = "rocket",
+            feature = "hyper"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn into_response_html(self) -> Html {
+        let mut html = self.into_fragment();
+        if html
+            .buf
+            .get(..5)
+            .is_some_and(|tag| tag.eq_ignore_ascii_case("<html"))
+        {
+            html.buf.insert_str(0, DOCTYPE);
+            for boundary in &mut html.boundaries {
+                *boundary += DOCTYPE.len();
+            }
+            for hole in &mut html.holes {
+                *hole = hole.moved_to(hole.offset + DOCTYPE.len());
+            }
+        }
+        html
+    }
+
     pub fn into_string(self) -> String {
-        Html::from(self).0
+        Html::from(self).into_string()
+    }
+
+    /// Writes the rendered HTML to `writer` while rendering, without
+    /// buffering the page, see [`IoWriter`].
+    ///
+    /// Like [`Html::from`], it starts with the `<!DOCTYPE html>`. `writer`
+    /// is flushed and returned at the end.
+    ///
+    /// ```
+    /// # use htmx::html;
+    /// let out = html! { <p>"Hello"</p> }.write_to(Vec::new()).unwrap();
+    /// assert_eq!(out, b"<!DOCTYPE html><p>Hello</p>");
+    /// ```
+    ///
+    /// # Errors
+    /// Errors if writing to `writer` fails.
+    pub fn write_to<W: io::Write + Send + 'static>(self, writer: W) -> io::Result<W> {
+        let mut html = Html::with_sink(IoWriter::new(writer));
+        html.write_str(DOCTYPE);
+        self.into_html(&mut html);
+        html.into_sink::<IoWriter<W>>()
+            .expect("sink should be the `IoWriter`")
+            .finish()
+    }
+
+    /// Renders into a string, preallocating space for at least `capacity`
+    /// bytes, see [`Html::with_capacity`].
+    pub fn into_string_with_capacity(self, capacity: usize) -> String {
+        let mut html = Html::with_capacity(capacity);
+        self.into_html(&mut html);
+        html.into_string()
     }
 
     pub fn into_html(self, html: &mut Html) {
         self.0(html);
     }
+
+    /// Creates a fragment rendering `self` followed by `other`.
+    ///
+    /// ```
+    /// # use htmx::html;
+    /// let header = html! { <h1>"Title"</h1> };
+    /// let page = header.then(html! { <p>"Content"</p> });
+    /// assert_eq!(
+    ///     page.into_fragment().to_string(),
+    ///     "<h1>Title</h1><p>Content</p>"
+    /// );
+    /// ```
+    pub fn then(self, other: impl IntoHtml) -> Fragment<impl FnOnce(&mut Html)> {
+        Fragment(move |html: &mut Html| {
+            self.into_html(html);
+            other.into_html(html);
+        })
+    }
+
+    /// Boxes the closure, allowing to store fragments created by different
+    /// closures together, e.g., to [`concat`] them.
+    pub fn boxed<'a>(self) -> BoxedFragment<'a>
+    where
+        F: 'a,
+    {
+        Fragment(Box::new(self.0))
+    }
+}
+
+/// Creates a fragment rendering all `fragments` in order.
+///
+/// As every closure has its own type, fragments need to be
+/// [`boxed`](Fragment::boxed) to be collected, e.g., into a [`Vec`].
+///
+/// ```
+/// # use htmx::{concat, html};
+/// let sections = vec![
+///     html! { <h1>"Title"</h1> }.boxed(),
+///     html! { <p>"Content"</p> }.boxed(),
+/// ];
+/// assert_eq!(
+///     concat(sections).into_fragment().to_string(),
+///     "<h1>Title</h1><p>Content</p>"
+/// );
+/// ```
+pub fn concat<I: IntoIterator>(fragments: I) -> Fragment<impl FnOnce(&mut Html)>
+where
+    I::Item: IntoHtml,
+{
+    Fragment(move |html: &mut Html| {
+        for fragment in fragments {
+            fragment.into_html(html);
+        }
+    })
 }
 
 impl<F: Fn(&mut Html)> Display for Fragment<F> {
@@ -651,7 +1920,7 @@ pub trait ToHtml {
     fn to_html(&self, html: &mut Html);
 }
 
-impl<T: ToHtml> ToHtml for &T {
+impl<T: ToHtml + ?Sized> ToHtml for &T {
     fn to_html(&self, html: &mut Html) {
         T::to_html(self, html);
     }
@@ -665,12 +1934,138 @@ impl<T: ToHtml> ToHtml for Option<T> {
     }
 }
 
+/// Renders either the value or the error.
+///
+/// This is intended for errors meant to be displayed, e.g., a validation
+/// message, not for silently rendering failures, handle those before
+/// rendering.
+impl<T: ToHtml, E: ToHtml> ToHtml for Result<T, E> {
+    fn to_html(&self, html: &mut Html) {
+        match self {
+            Ok(it) => it.to_html(html),
+            Err(err) => err.to_html(html),
+        }
+    }
+}
+
+// Renders the elements in order.
+impl<T: ToHtml> ToHtml for [T] {
+    fn to_html(&self, html: &mut Html) {
+        for it in self {
+            it.to_html(html);
+        }
+    }
+}
+
+impl<T: ToHtml, const N: usize> ToHtml for [T; N] {
+    fn to_html(&self, html: &mut Html) {
+        self.as_slice().to_html(html);
+    }
+}
+
+impl<T: ToHtml> ToHtml for Vec<T> {
+    fn to_html(&self, html: &mut Html) {
+        self.as_slice().to_html(html);
+    }
+}
+
+/// Renders every item of an iterator, e.g., the output of [`html!`] per
+/// item, which only implements [`IntoHtml`].
+///
+/// ```
+/// # use htmx::{html, List};
+/// let items = ["a", "b"];
+/// assert_eq!(
+///     html! {
+///         <ul>{List(items.iter().map(|item| html! { <li>{item}</li> }))}</ul>
+///     }
+///     .to_string(),
+///     "<!DOCTYPE html><ul><li>a</li><li>b</li></ul>"
+/// );
+/// ```
+pub struct List<I>(pub I);
+
+impl<I: IntoIterator> IntoHtml for List<I>
+where
+    I::Item: IntoHtml,
+{
+    fn into_html(self, html: &mut Html) {
+        for it in self.0 {
+            it.into_html(html);
+        }
+    }
+}
+
+// Renders the elements in order. Implementing `IntoHtml` instead of `ToHtml`
+// allows pieces that are only `IntoHtml`, e.g., the output of `html!`, to be
+// returned together from a function, a `ToHtml` impl for tuples would overlap
+// with the blanket `IntoHtml` impl.
+forr! { ($types:inner, $bounds:inner) in [
+    ((A), (A: IntoHtml)),
+    ((A, B), (A: IntoHtml, B: IntoHtml)),
+    ((A, B, C), (A: IntoHtml, B: IntoHtml, C: IntoHtml)),
+    ((A, B, C, D), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml)),
+    ((A, B, C, D, E), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml)),
+    ((A, B, C, D, E, F), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml)),
+    ((A, B, C, D, E, F, G), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml)),
+    ((A, B, C, D, E, F, G, H), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml, H: IntoHtml)),
+    ((A, B, C, D, E, F, G, H, I), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml, H: IntoHtml, I: IntoHtml)),
+    ((A, B, C, D, E, F, G, H, I, J), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml, H: IntoHtml, I: IntoHtml, J: IntoHtml)),
+    ((A, B, C, D, E, F, G, H, I, J, K), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml, H: IntoHtml, I: IntoHtml, J: IntoHtml, K: IntoHtml)),
+    ((A, B, C, D, E, F, G, H, I, J, K, L), (A: IntoHtml, B: IntoHtml, C: IntoHtml, D: IntoHtml, E: IntoHtml, F: IntoHtml, G: IntoHtml, H: IntoHtml, I: IntoHtml, J: IntoHtml, K: IntoHtml, L: IntoHtml)),
+] $*
+    impl<$bounds> IntoHtml for ($types,) {
+        #[allow(non_snake_case)]
+        fn into_html(self, html: &mut Html) {
+            let ($types,) = self;
+            forr! { #item:tt in [$types] #*
+                #item.into_html(html);
+            }
+        }
+    }
+}
+
+// Writes the elements in order.
+forr! { ($trait:ty, $fn:tt) in [(ToScript, to_script), (ToStyle, to_style)] $*
+    forr! { (#types:inner, #bounds:inner) in [
+        ((A), (A: $trait)),
+        ((A, B), (A: $trait, B: $trait)),
+        ((A, B, C), (A: $trait, B: $trait, C: $trait)),
+        ((A, B, C, D), (A: $trait, B: $trait, C: $trait, D: $trait)),
+        ((A, B, C, D, E), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait)),
+        ((A, B, C, D, E, F), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait)),
+        ((A, B, C, D, E, F, G), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait)),
+        ((A, B, C, D, E, F, G, H), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait, H: $trait)),
+        ((A, B, C, D, E, F, G, H, I), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait, H: $trait, I: $trait)),
+        ((A, B, C, D, E, F, G, H, I, J), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait, H: $trait, I: $trait, J: $trait)),
+        ((A, B, C, D, E, F, G, H, I, J, K), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait, H: $trait, I: $trait, J: $trait, K: $trait)),
+        ((A, B, C, D, E, F, G, H, I, J, K, L), (A: $trait, B: $trait, C: $trait, D: $trait, E: $trait, F: $trait, G: $trait, H: $trait, I: $trait, J: $trait, K: $trait, L: $trait)),
+    ] #*
+        impl<#bounds> $trait for (#types,) {
+            #[allow(non_snake_case)]
+            fn $fn(&self, html: &mut Html) {
+                let (#types,) = self;
+                let items: &[&dyn $trait] = &[#types];
+                for item in items {
+                    item.$fn(html);
+                }
+            }
+        }
+    }
+}
+
 impl ToHtml for RawSrc<'_> {
     fn to_html(&self, html: &mut Html) {
         html.write_str(&self.0);
     }
 }
 
+impl ToHtml for Trusted<'_> {
+    fn to_html(&self, html: &mut Html) {
+        html.write_str(&self.0);
+    }
+}
+
 impl ToScript for RawSrc<'_> {
     fn to_script(&self, html: &mut Html) {
         html.write_str(&self.0);
@@ -686,10 +2081,27 @@ impl ToStyle for RawSrc<'_> {
 /// CSS that can both be put [`html!`] or returned from an endpoint.
 pub struct Css<'a>(pub Cow<'a, str>);
 
+/// JS that can be put into a `<script>` in [`html!`], created by [`js!`].
+///
+/// It can also be used as an event handler attribute, e.g., `onclick`.
+#[derive(Display)]
+pub struct Script<'a>(pub Cow<'a, str>);
+
+impl ToScript for Script<'_> {
+    fn to_script(&self, out: &mut Html) {
+        self.0.to_script(out);
+    }
+}
+
+impl ToStyle for Css<'_> {
+    fn to_style(&self, out: &mut Html) {
+        self.0.to_style(out);
+    }
+}
+
 impl ToHtml for Css<'_> {
-    fn to_html(&self, _html: &mut Html) {
-        todo!()
-        // TODO: style::new(html).child(self.0.as_ref()).close();
+    fn to_html(&self, html: &mut Html) {
+        native::style::new(html).body(self);
     }
 }
 
@@ -701,17 +2113,75 @@ impl ElementState for Tag {
     }
 }
 
-forr! { $ty:ty in [CustomAttr, StyleAttr, ClassesAttr] $*
-    pub struct $ty;
+pub struct CustomAttr;
 
-    impl ElementState for $ty {
-        fn close_tag(html: &mut Html) {
-            html.write_quote();
-            html.write_gt();
+impl ElementState for CustomAttr {
+    fn close_tag(html: &mut Html) {
+        html.write_quote();
+        html.write_gt();
+    }
+}
+
+/// State of an element with an open `style` attribute, further declarations
+/// are added to it.
+pub struct StyleAttr;
+
+impl StyleAttr {
+    fn open(html: &mut Html) {
+        html.pending_attr = Some("style");
+    }
+
+    fn add(html: &mut Html, value: impl ToAttribute<Style>) {
+        if !value.is_unset() {
+            html.start_merged_value("; ");
+            value.write_inner(html);
+        }
+    }
+}
+
+impl ElementState for StyleAttr {
+    fn close_tag(html: &mut Html) {
+        Self::close_attr(html);
+        html.write_gt();
+    }
+}
+
+impl AttrState for StyleAttr {
+    fn close_attr(html: &mut Html) {
+        html.close_merged_attr();
+    }
+}
+
+/// State of an element with an open `class` attribute, further classes are
+/// added to it.
+pub struct ClassesAttr;
+
+impl ClassesAttr {
+    fn open(html: &mut Html) {
+        html.pending_attr = Some("class");
+    }
+
+    fn add(html: &mut Html, value: impl ToAttribute<Class>) {
+        if !value.is_unset() {
+            html.start_merged_value(" ");
+            value.write_inner(html);
         }
     }
 }
 
+impl ElementState for ClassesAttr {
+    fn close_tag(html: &mut Html) {
+        Self::close_attr(html);
+        html.write_gt();
+    }
+}
+
+impl AttrState for ClassesAttr {
+    fn close_attr(html: &mut Html) {
+        html.close_merged_attr();
+    }
+}
+
 pub struct Body;
 
 impl ElementState for Body {
@@ -722,6 +2192,17 @@ pub trait ElementState {
     fn close_tag(html: &mut Html);
 }
 
+/// States of an element whose start tag is open, i.e., that accept further
+/// attributes.
+pub trait AttrState: ElementState {
+    /// Closes a pending attribute, e.g., the merged `class` attribute.
+    fn close_attr(html: &mut Html);
+}
+
+impl AttrState for Tag {
+    fn close_attr(_: &mut Html) {}
+}
+
 forr! {$type:ty in [&str, String, Cow<'_, str>]$*
     impl ToHtml for $type {
         fn to_html(&self, out: &mut Html) {
@@ -742,12 +2223,79 @@ forr! {$type:ty in [&str, String, Cow<'_, str>]$*
     }
 }
 
+impl ToHtml for fmt::Arguments<'_> {
+    fn to_html(&self, html: &mut Html) {
+        Escaped(html)
+            .write_fmt(*self)
+            .expect("writing html should not fail");
+    }
+}
+
 impl ToHtml for char {
     fn to_html(&self, out: &mut Html) {
         write!(out, "{}", html_escape::encode_text(&self.to_string()));
     }
 }
 
+// The `Display` output of numbers and bools never needs escaping.
+forr! {$type:ty in [u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, isize, usize, bool]$*
+    impl ToHtml for $type {
+        fn to_html(&self, out: &mut Html) {
+            write!(out, "{self}");
+        }
+    }
+
+    impl ToScript for $type {
+        fn to_script(&self, out: &mut Html) {
+            write!(out, "{self}");
+        }
+    }
+
+    iff! {!equals($type)(bool) $:
+        impl ToStyle for $type {
+            fn to_style(&self, out: &mut Html) {
+                write!(out, "{self}");
+            }
+        }
+    }
+}
+
+// Rust writes non-finite floats as `inf` and `NaN`, scripts spell them
+// `Infinity` and `NaN`, CSS only has them as `calc()` constants.
+forr! {$type:ty in [f32, f64]$*
+    impl ToHtml for $type {
+        fn to_html(&self, out: &mut Html) {
+            write!(out, "{self}");
+        }
+    }
+
+    impl ToScript for $type {
+        fn to_script(&self, out: &mut Html) {
+            if self.is_finite() || self.is_nan() {
+                write!(out, "{self}");
+            } else if self.is_sign_positive() {
+                out.write_str("Infinity");
+            } else {
+                out.write_str("-Infinity");
+            }
+        }
+    }
+
+    impl ToStyle for $type {
+        fn to_style(&self, out: &mut Html) {
+            if self.is_finite() {
+                write!(out, "{self}");
+            } else if self.is_nan() {
+                out.write_str("calc(NaN)");
+            } else if self.is_sign_positive() {
+                out.write_str("calc(infinity)");
+            } else {
+                out.write_str("calc(-infinity)");
+            }
+        }
+    }
+}
+
 pub trait ToScript {
     fn to_script(&self, out: &mut Html);
 }
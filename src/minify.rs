@@ -0,0 +1,127 @@
+//! Whitespace collapsing of [`Html`].
+use html_escape::decode_html_entities;
+
+use crate::pretty::{Token, Tokens, RAW_ELEMENTS};
+use crate::{Html, WriteHtml};
+
+impl Html {
+    /// Collapses runs of ASCII whitespace in text to a single space, except in
+    /// `<pre>`, `<textarea>`, `<script>` and `<style>`.
+    ///
+    /// Whitespace is never removed completely, as it is significant between
+    /// inline elements. Attribute values are unquoted where
+    /// [allowed](WriteHtml::write_attr_value_minified), tags containing
+    /// [placeholders](crate::Placeholder) and comments are kept unchanged.
+    /// [Flush boundaries](Self::flush) between tags and placeholders are kept.
+    ///
+    /// ```
+    /// # use htmx::{html, Html};
+    /// let html = Html::from(html! {
+    ///     <p class="note">"Some   text\n    over lines "</p>
+    ///     <pre title="Keep this">"  keep\n  this"</pre>
+    /// });
+    /// assert_eq!(
+    ///     html.minified().to_string(),
+    ///     "<!DOCTYPE html><p class=note>Some text over lines </p><pre title=\"Keep this\">  keep\n  \
+    ///      this</pre>"
+    /// );
+    /// ```
+    pub fn minified(self) -> Self {
+        let mut output = String::with_capacity(self.buf.len());
+        let mut boundaries = Vec::with_capacity(self.boundaries.len());
+        let mut pending = self.boundaries.iter().copied().peekable();
+        let mut holes = Vec::with_capacity(self.holes.len());
+        let mut pending_holes = self.holes.iter().copied().peekable();
+        let mut position = 0;
+        let mut raw = false;
+        for token in Tokens::new(&self.buf) {
+            // Boundaries are mapped to the start of the token containing them.
+            while pending.next_if(|&boundary| boundary <= position).is_some() {
+                boundaries.push(output.len());
+            }
+            let start = position;
+            position += token.as_str().len();
+            match token {
+                Token::Text(mut text) if !raw => {
+                    // Text is collapsed separately around placeholders, as
+                    // their value is not known yet.
+                    let mut text_start = start;
+                    while let Some(hole) = pending_holes.next_if(|hole| hole.offset < position) {
+                        let (before, after) = text.split_at(hole.offset - text_start);
+                        collapse_whitespace(&mut output, before);
+                        holes.push(hole.moved_to(output.len()));
+                        text = after;
+                        text_start = hole.offset;
+                    }
+                    collapse_whitespace(&mut output, text);
+                }
+                Token::Open(_, tag)
+                    if pending_holes
+                        .peek()
+                        .map_or(true, |hole| hole.offset >= position) =>
+                {
+                    unquote_attributes(&mut output, tag);
+                }
+                token => {
+                    while let Some(hole) = pending_holes.next_if(|hole| hole.offset < position) {
+                        holes.push(hole.moved_to(output.len() + hole.offset - start));
+                    }
+                    output.push_str(token.as_str());
+                }
+            }
+            raw = matches!(token, Token::Open(name, _) if RAW_ELEMENTS
+                .iter()
+                .any(|raw| raw.eq_ignore_ascii_case(name)));
+        }
+        boundaries.extend(pending.map(|_| output.len()));
+        boundaries.dedup();
+        holes.extend(pending_holes.map(|hole| hole.moved_to(output.len())));
+        Self {
+            buf: output,
+            boundaries: boundaries.into(),
+            holes,
+            nonce: self.nonce,
+            refs: self.refs,
+            sender: self.sender,
+        }
+    }
+}
+
+/// Writes the open `tag` with its attribute values written using
+/// [`WriteHtml::write_attr_value_minified`].
+fn unquote_attributes(output: &mut String, tag: &str) {
+    let mut rest = tag;
+    // Values are always quoted with `"` and have `"` encoded.
+    while let Some(start) = rest.find("=\"") {
+        let Some(len) = rest[start + 2..].find('"') else {
+            break;
+        };
+        let value = &rest[start + 2..start + 2 + len];
+        output.push_str(&rest[..start]);
+        // `<a href=/>` would look like a self-closing tag.
+        if value.ends_with('/') {
+            output.push_str(&rest[start..start + 3 + len]);
+        } else {
+            let mut html = Html::empty();
+            html.write_attr_value_minified(decode_html_entities(value));
+            output.push_str(&html.buf);
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    output.push_str(rest);
+}
+
+fn collapse_whitespace(output: &mut String, text: &str) {
+    let mut whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !whitespace {
+                output.push(' ');
+            }
+            whitespace = true;
+        } else {
+            output.push(c);
+            whitespace = false;
+        }
+    }
+}
@@ -6,61 +6,116 @@ use std::marker::PhantomData;
 
 use forr::{forr, iff};
 
-use crate::attributes::{Any, DateTime, FlagOrValue, Number, TimeDateTime, ToAttribute};
-use crate::{ElementState, Html, IntoHtml, IntoScript, IntoStyle, Tag, Fragment};
+use crate::attributes::{
+    AttributeValue, BoolValue, Class, DateTime, FlagOrAttributeValue, Number, Rel, Style, Swap,
+    Target, TimeDateTime, ToAttribute, YesNo,
+};
+use crate::{
+    AttrState, ClassesAttr, ElementState, Fragment, Html, IntoHtml, IntoScript, IntoStyle, Tag,
+};
 
 macro_rules! attribute {
-    ($elem:ident|$name:ident<FlagOrAttributeValue>) => {
-        attribute!($elem, $name, stringify!($name), impl FlagOrAttributeValue);
+    ($ret:tt $elem:ident|$name:ident<FlagOrAttributeValue>) => {
+        attribute!($ret $elem, $name, stringify!($name), impl FlagOrAttributeValue);
     };
-    ($elem:ident|$name:ident<TimeDateTime>) => {
-        attribute!($elem, $name, stringify!($name), impl TimeDateTime);
+    ($ret:tt $elem:ident|$name:ident<TimeDateTime>) => {
+        attribute!($ret $elem, $name, stringify!($name), impl TimeDateTime);
     };
-    ($elem:ident|$name:ident) => {
-        attribute!($elem|$name<String>);
+    ($ret:tt $elem:ident|$name:ident) => {
+        attribute!($ret $elem|$name<String>);
     };
-    ($elem:ident|$name:ident=$actual:tt) => {
-        attribute!($elem|$name=$actual<String>);
+    ($ret:tt $elem:ident|$name:ident=$actual:tt) => {
+        attribute!($ret $elem|$name=$actual<String>);
     };
-    ($elem:ident|$name:ident < $type:ty >) => {
-        attribute!($elem, $name, stringify!($name), impl ToAttribute<$type>);
+    ($ret:tt $elem:ident|$name:ident < $type:ty >) => {
+        attribute!($ret $elem, $name, stringify!($name), impl ToAttribute<$type>);
     };
-    ($elem:ident|$name:ident=$actual:tt< $type:ty >) => {
-        attribute!($elem, $name, $actual, impl ToAttribute<$type>);
+    ($ret:tt $elem:ident|$name:ident=$actual:tt< $type:ty >) => {
+        attribute!($ret $elem, $name, $actual, impl ToAttribute<$type>);
     };
-    (global, $name:ident, $actual:expr, $type:ty) => {
-        attr_fn!(concat!("Sets the [`", $actual, "`](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/", $actual, ") attribute."), $name, $actual, $type);
+    ($ret:tt global, $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the [`", $actual, "`](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/", $actual, ") attribute."), $name, $actual, $type);
     };
-    (event, $name:ident, $actual:expr, $type:ty) => {
-        attr_fn!(concat!("Sets the `", $actual, "` [event handler](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes#event_handler_attributes) attribute."), $name, $actual, $type);
+    ($ret:tt aria, $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the [`", $actual, "`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/", $actual, ") attribute."), $name, $actual, $type);
     };
-    ($elem:ident, $name:ident, $actual:expr, $type:ty) => {
-        attr_fn!(concat!("Sets the `", $actual, "` attribute on the [`<", stringify!($elem),">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($elem), "#attributes) element."), $name, $actual, $type);
+    ($ret:tt htmx, $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the [`", $actual, "`](https://htmx.org/attributes/", $actual, "/) attribute."), $name, $actual, $type);
+    };
+    ($ret:tt event, $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the `", $actual, "` [event handler](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes#event_handler_attributes) attribute."), $name, $actual, $type);
+    };
+    ($ret:tt $elem:ident, $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the `", $actual, "` attribute on the [`<", stringify!($elem),">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($elem), "#attributes) element."), $name, $actual, $type);
     };
 }
 
 macro_rules! attr_fn{
-    ($($doc:expr)?, $name:ident, $actual:tt, $type:ty) => {
+    ([@forward] $($doc:expr)?, $name:ident, $actual:tt, $type:ty) => {
+        $(#[doc = $doc])?
+        #[must_use]
+        fn $name(self, value: $type) -> Self {
+            self.forward_attr(|html| {
+                if !value.is_unset() {
+                    html.write_attr_key($actual);
+                    value.write(html);
+                }
+            })
+        }
+    };
+    ([$ret:ty] $($doc:expr)?, $name:ident, $actual:tt, $type:ty) => {
         $(#[doc = $doc])?
-        pub fn $name(mut self, value: $type) -> Self {
+        pub fn $name(self, value: $type) -> $ret {
+            let this = self.into_tag();
             if !value.is_unset() {
-                write!(self.html, " {}", $actual);
-                value.write(&mut self.html);
+                this.html.write_attr_key($actual);
+                value.write(this.html);
             }
-            self
+            this
         }
-    }
+    };
+}
+
+// Attributes shared by all elements, `$callback` is invoked for each group.
+// Components with a `tag` forward them, see `__private::ForwardAttrs`.
+macro_rules! global_attributes {
+    ($callback:ident!($($args:tt)*)) => {
+        // Global attributes
+        $callback!($($args)* global [
+            accesskey<char>, autocapitalize/*off/none, on/sentence, words, characters*/, autofocus<bool>, contenteditable<BoolValue>/*true, false, plaintext-only*/, dir/*ltr,rtl,auto*/, draggable/*true,false*/, enterkeyhint,hidden<FlagOrAttributeValue>/*hidden|until-found*/, id, inert<bool>, inputmode/*none,text,decimal,numeric,tel,search,email,url*/, is, itemid, itemprop, itemref, itemscope, itemtype, lang, nonce, part, popover<FlagOrAttributeValue>/*auto|manual*/, role, slot, spellcheck<BoolValue>, style<Style>, tabindex, title, translate<YesNo>, virtualkeyboardpolicy/*auto,manual*/
+        ]);
+        // ARIA attributes
+        $callback!($($args)* aria [
+            aria_activedescendant="aria-activedescendant", aria_atomic="aria-atomic"<BoolValue>, aria_autocomplete="aria-autocomplete"/*none,inline,list,both*/, aria_braillelabel="aria-braillelabel", aria_brailleroledescription="aria-brailleroledescription", aria_busy="aria-busy"<BoolValue>, aria_checked="aria-checked"<BoolValue>/*true,false,mixed*/, aria_colcount="aria-colcount"<Number>, aria_colindex="aria-colindex"<Number>, aria_colindextext="aria-colindextext", aria_colspan="aria-colspan"<Number>, aria_controls="aria-controls", aria_current="aria-current"<BoolValue>/*page,step,location,date,time,true,false*/, aria_describedby="aria-describedby", aria_description="aria-description", aria_details="aria-details", aria_disabled="aria-disabled"<BoolValue>, aria_errormessage="aria-errormessage", aria_expanded="aria-expanded"<BoolValue>, aria_flowto="aria-flowto", aria_haspopup="aria-haspopup"<BoolValue>/*true,false,menu,listbox,tree,grid,dialog*/, aria_hidden="aria-hidden"<BoolValue>, aria_invalid="aria-invalid"<BoolValue>/*true,false,grammar,spelling*/, aria_keyshortcuts="aria-keyshortcuts", aria_label="aria-label", aria_labelledby="aria-labelledby", aria_level="aria-level"<Number>, aria_live="aria-live"/*off,polite,assertive*/, aria_modal="aria-modal"<BoolValue>, aria_multiline="aria-multiline"<BoolValue>, aria_multiselectable="aria-multiselectable"<BoolValue>, aria_orientation="aria-orientation"/*horizontal,vertical*/, aria_owns="aria-owns", aria_placeholder="aria-placeholder", aria_posinset="aria-posinset"<Number>, aria_pressed="aria-pressed"<BoolValue>/*true,false,mixed*/, aria_readonly="aria-readonly"<BoolValue>, aria_relevant="aria-relevant"/*additions,all,removals,text*/, aria_required="aria-required"<BoolValue>, aria_roledescription="aria-roledescription", aria_rowcount="aria-rowcount"<Number>, aria_rowindex="aria-rowindex"<Number>, aria_rowindextext="aria-rowindextext", aria_rowspan="aria-rowspan"<Number>, aria_selected="aria-selected"<BoolValue>, aria_setsize="aria-setsize"<Number>, aria_sort="aria-sort"/*none,ascending,descending,other*/, aria_valuemax="aria-valuemax"<Number>, aria_valuemin="aria-valuemin"<Number>, aria_valuenow="aria-valuenow"<Number>, aria_valuetext="aria-valuetext"
+        ]);
+        // Event handlers
+        $callback!($($args)* event [
+            onabort, onanimationcancel, onanimationend, onanimationiteration, onanimationstart, onauxclick, onautocomplete, onautocompleteerror, onbeforeinput, onbeforetoggle, onblur, oncancel, oncanplay, oncanplaythrough, onchange, onclick, onclose, oncontextmenu, oncopy, oncuechange, oncut, ondblclick, ondrag, ondragend, ondragenter, ondragleave, ondragover, ondragstart, ondrop, ondurationchange, onemptied, onended, onerror, onfocus, onfocusin, onfocusout, onformdata, oninput, oninvalid, onkeydown, onkeypress, onkeyup, onload, onloadeddata, onloadedmetadata, onloadstart, onmousedown, onmouseenter, onmouseleave, onmousemove, onmouseout, onmouseover, onmouseup, onmousewheel, onpaste, onpause, onplay, onplaying, onpointercancel, onpointerdown, onpointerenter, onpointerleave, onpointermove, onpointerout, onpointerover, onpointerup, onprogress, onratechange, onreset, onresize, onscroll, onscrollend, onseeked, onseeking, onselect, onshow, onsort, onstalled, onsubmit, onsuspend, ontimeupdate, ontoggle, ontouchcancel, ontouchend, ontouchmove, ontouchstart, ontransitioncancel, ontransitionend, ontransitionrun, ontransitionstart, onvolumechange, onwaiting, onwheel
+        ]);
+        // Typed htmx attributes, others are set via `custom_attr`
+        $callback!($($args)* htmx [hx_swap="hx-swap"<Swap>, hx_target="hx-target"<Target>]);
+    };
+}
+
+// Defines the setters for a group of `global_attributes`, returning `$ret`,
+// or forwarding them for `[@forward]`.
+macro_rules! attributes {
+    ($ret:tt $kind:ident [$($attr:tt)*]) => {
+        forr! { #attr:ty in [$($attr)*] #*
+            attribute!($ret $kind|#attr);
+        }
+    };
 }
 
 // Attributes that take values
 forr! { ($type:ty, $attrs:tt) in [
-    (a, [download<FlagOrValue<String>>, href, hreflang, ping, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, rel, target/*_self|_blank|_parent|_top|...*/, type_="type"]),
-    (area, [alt, coords, download<FlagOrValue<String>>, href, ping, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, rel, shape, target]),
-    (audio, [autoplay<bool>, controls<bool>, crossorigin/*anonymous, use-credentials*/, loop_="loop", muted<bool>, preload/*none,metadata,auto*/, src]),
+    (a, [download<FlagOrAttributeValue>, href, hreflang, ping, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, rel<Rel>, target/*_self|_blank|_parent|_top|...*/, type_="type"]),
+    (area, [alt, coords, download<FlagOrAttributeValue>, href, ping, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, rel<Rel>, shape, target]),
+    (audio, [autoplay<bool>, controls<bool>, crossorigin<FlagOrAttributeValue>/*anonymous, use-credentials*/, loop_="loop", muted<bool>, preload/*none,metadata,auto*/, src]),
     (base, [href, target/*_self|_blank|_parent|_top|...*/]),
     (blockquote, [cite]),
     (body, [onafterprint, onbefroeprint, onbeforeunload, onhashchange, onlanguagechange, onmessage, onoffline, ononline, onpopstate, onstorage, onundo, onunload]),
-    (form, [accept_charset="accept-charset", autocomplete/*off|on*/, name, rel/*enum[]*/, action, enctype/*application/x-www-form-urlencoded, multipart/form-data, text/plain*/, method/*post|get|dialogp*/, novalidate<bool>, target/*_self|_blank|_parent|_top|...*/]),
+    (form, [accept_charset="accept-charset", autocomplete/*off|on*/, name, rel<Rel>, action, enctype/*application/x-www-form-urlencoded, multipart/form-data, text/plain*/, method/*post|get|dialogp*/, novalidate<bool>, target/*_self|_blank|_parent|_top|...*/]),
     (button, [disabled<bool>, form, formaction, formenctype/*^^*/, formmethod/*^^*/, formnovalidate<bool>, formtarget/*^^*/, name, popovertarget, popovertargetaction/*hide|show|toggle*/, type_="type"/*submit|reset|button*/, value]),
     (canvas, [height<Number>, width<Number>]),
     (col, [span<Number>]),
@@ -73,15 +128,15 @@ forr! { ($type:ty, $attrs:tt) in [
     (fieldset, [disabled<bool>, form, name]),
     (html, [xmlns]),
     (iframe, [allow, height<Number>, loading/*eager, lazy*/, name, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, sandbox/*allow-downloads,allow-forms,allow-modals,allow-orientation-lock,allow-pointer-lock,allow-popups,allow-popups-to-escape-sandbox,allow-presentation,allow-same-origin,allow-scripts,allow-top-navigation,allow-top-navigation-by-user-activation,allow-top-navigation-to-custom-protocols*/, src, srcdoc, width<Number>]),
-    (img, [crossorigin/*anonymous, use-credentials*/, decoding/*sync,async,auto*/,elementtiming,height<Number>,ismap<bool>, loading/*eager, lazy*/, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, sizes, src, srcset, width, usemap]),
-    // TODO consider differentiating types
-    (input, [accept, alt, autocomplete, capture, checked, disabled<bool>, form, formaction, formenctype/*^^*/, formmethod/*^^*/, formnovalidate<bool>, formtarget/*^^*/, height, max, maxlength, min, minlength, multiple, name, pattern, placeholder, popovertarget, popovertargetaction/*hide|show|toggle*/, readonly<bool>, required<bool>, size, src, step, type_="type"/*submit|reset|button*/, value, width]),
+    (img, [crossorigin<FlagOrAttributeValue>/*anonymous, use-credentials*/, decoding/*sync,async,auto*/,elementtiming,height<Number>,ismap<bool>, loading/*eager, lazy*/, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, sizes, src, srcset, width, usemap]),
+    // Typed variants are created by `type_`, see `input_type`.
+    (input, [accept, alt, autocomplete, capture, checked, disabled<bool>, form, formaction, formenctype/*^^*/, formmethod/*^^*/, formnovalidate<bool>, formtarget/*^^*/, height, max, maxlength, min, minlength, multiple, name, pattern, placeholder, popovertarget, popovertargetaction/*hide|show|toggle*/, readonly<bool>, required<bool>, size, src, step, value, width]),
     (ins, [cite, datetime<DateTime>]),
     (label, [for_="for"]),
     (li, [value]),
-    (link, [as_="as", crossorigin/*anonymous, use-credentials*/, disabled, href, hreflang, imagesizes, imagesrcset, integrity, media, referrerpolicy/*no-referrer,no-referrer-when-downgrade,origin,origin-when-cross-origin,unsafe-url*/, rel, type_="type"]),
+    (link, [as_="as", crossorigin<FlagOrAttributeValue>/*anonymous, use-credentials*/, disabled, href, hreflang, imagesizes, imagesrcset, integrity, media, referrerpolicy/*no-referrer,no-referrer-when-downgrade,origin,origin-when-cross-origin,unsafe-url*/, rel<Rel>, type_="type"]),
     (map, [name]),
-    (meta, [charset, content, http_equiv="http-equiv"/*content-security-policy,content-type,default-style,x-ua-compatible,refresh*/, name]),
+    (meta, [charset, content, http_equiv="http-equiv"/*content-security-policy,content-type,default-style,x-ua-compatible,refresh*/, name, property/*Open Graph, e.g., og:title*/]),
     (meter, [value<Number>, min<Number>, max<Number>, low<Number>, high<Number>, optimum<Number>, form]),
     (object, [data, form, height<Number>, name, type_="type", usemap, width<Number>]),
     (ol, [reversed<bool>, start<Number>, type_="type"/*a,A,i,I,1*/]),
@@ -90,7 +145,7 @@ forr! { ($type:ty, $attrs:tt) in [
     (output, [for_="for", form, name]),
     (progress, [max<Number>, value<Number>]),
     (q, [cite]),
-    (script, [async_="async"<bool>, crossorigin/*anonymous|use-credentials*/, defer<bool>, integrity, nomodule<bool>, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, src, type_="type"/*importmap|module|Mime*/]),
+    (script, [async_="async"<bool>, crossorigin<FlagOrAttributeValue>/*anonymous|use-credentials*/, defer<bool>, integrity, nomodule<bool>, referrerpolicy/*no-referrer|no-referrer-when-downgrade|origin|origin-when-cross-origin|same-origin|strict-origin|strict-origin-when-cross-origin|unsafe-url*/, src, type_="type"/*importmap|module|Mime*/]),
     (select, [ autocomplete, disabled<bool>, form, name, required<bool>, size]),
     (slot, [name]),
     (source, [type_="type", src, srcset, sizes, media, height<Number>, width<Number>]),
@@ -100,21 +155,34 @@ forr! { ($type:ty, $attrs:tt) in [
     (th, [colspan<Number>, headers, rowspan<Number>, scope/*row,col,rowgroup,colgroup*/]),
     (time, [datetime<TimeDateTime>]),
     (track, [default<bool>, kind/*subtitles,captions,descriptions,chapters,metadata*/, label, src, srclang]),
-    (video, [autoplay<bool>, controls<bool>, crossorigin/*anonymous, use-credentials*/, height<Number>, loop_="loop"<bool>, muted<bool>, playsinline<bool>, poster, preload/*none,metadata,auto*/, src, width<Number>])
+    (video, [autoplay<bool>, controls<bool>, crossorigin<FlagOrAttributeValue>/*anonymous, use-credentials*/, height<Number>, loop_="loop"<bool>, muted<bool>, playsinline<bool>, poster, preload/*none,metadata,auto*/, src, width<Number>])
 ] $*
-    impl $type<'_, Tag> {
+    impl<'html, Attr: AttrState> $type<'html, Attr> {
         forr! { $attr:ty in $attrs $*
-            attribute!($type|$attr);
+            attribute!([$type<'html, Tag>] $type|$attr);
         }
     }
 }
 
 forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi, bdo, blockquote, body, br, button, canvas, caption, cite, code, col, colgroup, data, datalist, dd, del, details, dfn, dialog, dl, dt, em, embeded, div, fieldset, figcaption, figure, footer, form, h1, h2, h3, h4, h5, h6, head, header, hgroup, hr, html, i, iframe, img, input, ins, kbd, label, legend, li, link, main, map, mark, menu, meta, meter, nav, noscript, object, ol, optgroup, option, output, p, picture, pre, progress, q, rp, rt, ruby, s, samp, script, search, section, select, slot, small, source, span, strong, style, sub, summary, sup, table, tbody, td, template, textarea, tfoot, th, thead, time, title, tr, track, u, ul, var, video, wbr, xmp] $*
 
-    #[doc = concat!("The [`<", stringify!($type), ">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($type), ") element.")]
-    pub struct $type<'html, Attr: ElementState> {
-        html: &'html mut Html,
-        state: PhantomData<Attr>
+    iff! {!equals($type)(input) $:
+        #[doc = concat!("The [`<", stringify!($type), ">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($type), ") element.")]
+        pub struct $type<'html, Attr: ElementState, Kind = ()> {
+            html: &'html mut Html,
+            state: PhantomData<(Attr, Kind)>
+        }
+    }
+
+    iff! {equals($type)(input) $:
+        #[doc = concat!("The [`<", stringify!($type), ">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($type), ") element.")]
+        ///
+        /// Setting [`type_`](Self::type_) to one of the kinds in [`input_type`]
+        /// only exposes the attributes valid for that kind.
+        pub struct $type<'html, Attr: ElementState, Kind = input_type::Untyped> {
+            html: &'html mut Html,
+            state: PhantomData<(Attr, Kind)>
+        }
     }
 
     impl $type<'_, Tag> {
@@ -126,12 +194,26 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
 
         pub fn new(html: &'html mut Html) -> Self {
             html.write_open_tag_unchecked(stringify!($type));
+            iff! {equals_any($type)[(script), (style)] $:
+                html.write_nonce();
+            }
             Self {
                 html: html,
                 state: PhantomData
             }
         }
+    }
 
+    // Attributes can be set in any state with an open tag, pending `class`
+    // attributes are closed first.
+    impl<'html, Attr: AttrState, Kind> $type<'html, Attr, Kind> {
+        fn into_tag(self) -> $type<'html, Tag, Kind> {
+            Attr::close_attr(self.html);
+            $type {
+                html: self.html,
+                state: PhantomData
+            }
+        }
 
         // iff! {equals($type)(script) $:
         //     /// Adds JS code to the script.
@@ -155,7 +237,7 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
         ///
         /// # Panics
         /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
-        pub fn custom_attr( self, key: impl Display, value: impl ToAttribute<Any>) -> Self {
+        pub fn custom_attr(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag, Kind> {
             assert!(!key.to_string().chars().any(|c| c.is_whitespace()
                 || c.is_control()
                 || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
@@ -183,14 +265,45 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
         /// Useful for setting, e.g., `data-{key}`.
         ///
         /// Note: This function does contain the check for [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0) only in debug builds, failing to ensure valid keys can lead to broken HTML output.
-        pub fn custom_attr_unchecked(mut self, key: impl Display, value: impl ToAttribute<Any>) -> Self
+        pub fn custom_attr_unchecked(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag, Kind>
         {
             debug_assert!(!key.to_string().chars().any(|c| c.is_whitespace()
                 || c.is_control()
                 || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
-            write!(self.html, " {key}");
-            value.write(&mut self.html);
-            self
+            let this = self.into_tag();
+            if !value.is_unset() {
+                this.html.write_attr_key(key);
+                value.write(this.html);
+            }
+            this
+        }
+
+        /// Sets multiple custom attributes, e.g., forwarded from a component.
+        ///
+        /// In [`rtml!`](crate::rtml) this can be written as `..attrs`.
+        ///
+        /// # Panics
+        /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+        pub fn custom_attrs<K: Display, V: AttributeValue>(self, attrs: impl IntoIterator<Item = (K, V)>) -> $type<'html, Tag, Kind> {
+            let mut this = self.into_tag();
+            for (key, value) in attrs {
+                this = this.custom_attr(key, value);
+            }
+            this
+        }
+
+        // `<object>` has a `data` attribute.
+        iff! {!equals($type)(object) $:
+            /// Sets the `data-{key}` attribute, e.g., to pass values to JS.
+            ///
+            /// In [`html!`](crate::html) this can be written as `data_key=value` or
+            /// `data::key=value`, `_` are translated to `-`.
+            ///
+            /// # Panics
+            /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+            pub fn data(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag, Kind> {
+                self.custom_attr(format_args!("data-{key}"), value)
+            }
         }
 
         // TODO
@@ -208,13 +321,6 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
         //     self.change_state()
         // }
 
-        // TODO
-        // /// Adds classes to the element.
-        // pub fn class(mut self, value: impl ToAttribute<) -> $type<T, ClassesAttr> {
-        //     write!(self.html, " classes=\"");
-        //     self.change_state()
-        // }
-
         // TODO
         // /// Adds styles to the element.
         // pub fn style(mut self) -> $type<T, StyleAttr> {
@@ -222,24 +328,12 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
         //     self.change_state()
         // }
 
-        // Global attributes
-        // TODO class should be able to specify multiple times
-        forr! { $attr:ty in [
-            // TODO ARIA: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes
-            class, accesskey<char>, autocapitalize/*off/none, on/sentence, words, characters*/, autofocus<bool>, contenteditable/*true, false, plaintext-only*/, dir/*ltr,rtl,auto*/, draggable/*true,false*/, enterkeyhint,hidden<FlagOrValue<String>>/*hidden|until-found*/, id, inert<bool>, inputmode/*none,text,decimal,numeric,tel,search,email,url*/, is, itemid, itemprop, itemref, itemscope, itemtype, lang, nonce, part, popover, rolle, slot, spellcheck<FlagOrValue<String>>/*true,false*/, tabindex, title, translate/*yes,no*/, virtualkeyboardpolicy/*auto,manual*/] $*
-            attribute!(global|$attr);
-        }
-        // Event handlers
-        forr! { $attr:ty in [
-            onabort, onautocomplete, onautocompleteerror, onblur, oncancel, oncanplay, oncanplaythrough, onchange, onclick, onclose, oncontextmenu, oncuechange, ondblclick, ondrag, ondragend, ondragenter, ondragleave, ondragover, ondragstart, ondrop, ondurationchange, onemptied, onended, onerror, onfocus, oninput, oninvalid, onkeydown, onkeypress, onkeyup, onload, onloadeddata, onloadedmetadata, onloadstart, onmousedown, onmouseenter, onmouseleave, onmousemove, onmouseout, onmouseover, onmouseup, onmousewheel, onpause, onplay, onplaying, onprogress, onratechange, onreset, onresize, onscroll, onseeked, onseeking, onselect, onshow, onsort, onstalled, onsubmit, onsuspend, ontimeupdate, ontoggle, onvolumechange, onwaiting
-        ] $*
-            attribute!(event|$attr);
-        }
+        global_attributes!(attributes!([$type<'html, Tag, Kind>]));
     }
 
     iff! {!equals_any($type)[(area), (base), (br), (col), (embeded), (hr), (input), (link), (meta), (source), (track), (wbr)] $:
 
-        impl <Attr: ElementState> $type<'_, Attr> {
+        impl <Attr: ElementState, Kind> $type<'_, Attr, Kind> {
             iff! {equals($type)(script) $:
                 pub fn body(mut self, body: impl IntoScript) -> impl IntoHtml {
                     Attr::close_tag(&mut self.html);
@@ -268,7 +362,7 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
             }
         }
 
-        impl <Attr: ElementState> $type<'_, Attr> {
+        impl <Attr: ElementState, Kind> $type<'_, Attr, Kind> {
             pub fn close(self) -> impl IntoHtml {
                 self.body(::htmx::Fragment::EMPTY)
             }
@@ -276,7 +370,7 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
     }
 
     iff! {equals_any($type)[(area), (base), (br), (col), (embeded), (hr), (input), (link), (meta), (source), (track), (wbr)] $:
-        impl <Attr: ElementState> $type<'_, Attr> {
+        impl <Attr: ElementState, Kind> $type<'_, Attr, Kind> {
             pub fn close(mut self) -> impl IntoHtml {
                 Attr::close_tag(&mut self.html);
                 Fragment::EMPTY
@@ -285,8 +379,37 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
     }
 
 
+    impl<'html, Kind> $type<'html, Tag, Kind> {
+        /// Adds classes to the element.
+        ///
+        /// Can be called multiple times, all classes are merged into a single
+        /// `class` attribute, which is closed by setting any other attribute.
+        pub fn class(self, value: impl ToAttribute<Class>) -> $type<'html, ClassesAttr, Kind> {
+            ClassesAttr::open(self.html);
+            ClassesAttr::add(self.html, value);
+            $type {
+                html: self.html,
+                state: PhantomData
+            }
+        }
+    }
+
+    impl<'html, Kind> $type<'html, ClassesAttr, Kind> {
+        /// Adds classes to the element.
+        pub fn class(self, value: impl ToAttribute<Class>) -> Self {
+            ClassesAttr::add(self.html, value);
+            self
+        }
+
+        /// Closes the `class` attribute, allowing to set other attributes.
+        #[must_use]
+        pub fn close_attr(self) -> $type<'html, Tag, Kind> {
+            self.into_tag()
+        }
+    }
+
     // TODO
-    // forr! {$Attr:ty in [CustomAttr, ClassesAttr, StyleAttr] $*
+    // forr! {$Attr:ty in [CustomAttr, StyleAttr] $*
     //     impl $type<$Attr> {
     //         pub fn add(mut self, value: impl Display) -> Self {
     //             write!(self.html, "; {value}");
@@ -300,3 +423,100 @@ forr! { $type:ty in [a, abbr, address, area, article, aside, audio, b, base, bdi
     //     }
     // }
 }
+
+/// Kinds of [`<input>`](input) elements, passed to [`input::type_`].
+///
+/// A typed `<input>` only exposes the attributes that are valid for its kind,
+/// e.g., a [`Checkbox`](input_type::Checkbox) can be `checked` but has no
+/// `step`:
+///
+/// ```
+/// # use htmx::html;
+/// html! {
+///     <input type=input_type::Checkbox name="remember" checked/>
+///     <input type=input_type::Number name="amount" min=0 max=10/>
+/// };
+/// ```
+///
+/// ```compile_fail,E0599
+/// # use htmx::html;
+/// html! { <input type=input_type::Checkbox step="2"/> };
+/// ```
+///
+/// Any other value keeps the untyped `<input>` that accepts all attributes.
+pub mod input_type {
+    use super::*;
+    use crate::attributes;
+
+    /// Value accepted by [`input::type_`], determining the returned `Kind`.
+    pub trait InputType {
+        /// Kind of the `<input>` after setting this type.
+        type Kind;
+        /// Writes the attribute value.
+        fn write(&self, html: &mut Html);
+        /// Whether the attribute is unset.
+        fn is_unset(&self) -> bool {
+            false
+        }
+    }
+
+    /// An `<input>` that accepts all attributes.
+    pub struct Untyped;
+
+    impl<T: ToAttribute<String>> InputType for T {
+        type Kind = Untyped;
+
+        fn write(&self, html: &mut Html) {
+            ToAttribute::write(self, html);
+        }
+
+        fn is_unset(&self) -> bool {
+            ToAttribute::is_unset(self)
+        }
+    }
+
+    forr! { ($kind:ty, $value:expr, $attrs:tt) in [
+        (Text, "text", [autocomplete, dirname, maxlength<attributes::Number>, minlength<attributes::Number>, pattern, placeholder, readonly<bool>, required<bool>, size<attributes::Number>, value]),
+        (Number, "number", [autocomplete, max<attributes::Number>, min<attributes::Number>, placeholder, readonly<bool>, required<bool>, step, value]),
+        (Checkbox, "checkbox", [checked<bool>, required<bool>, value]),
+        (Date, "date", [autocomplete, max, min, readonly<bool>, required<bool>, step, value])
+    ] $*
+        #[doc = concat!("An [`<input type=\"", $value, "\">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input/", $value, ").")]
+        pub struct $kind;
+
+        impl InputType for $kind {
+            type Kind = $kind;
+
+            fn write(&self, html: &mut Html) {
+                html.write_attr_value_unchecked($value);
+            }
+        }
+
+        impl<'html, Attr: AttrState> input<'html, Attr, $kind> {
+            forr! { $attr:ty in [disabled<bool>, form, name] $*
+                attribute!([input<'html, Tag, $kind>] input|$attr);
+            }
+            forr! { $attr:ty in $attrs $*
+                attribute!([input<'html, Tag, $kind>] input|$attr);
+            }
+        }
+    }
+
+    impl<'html, Attr: AttrState> input<'html, Attr> {
+        /// Sets the `type` attribute on the [`<input>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input#attributes) element.
+        ///
+        /// Passing a kind from [`input_type`](self) returns a typed `<input>`.
+        #[allow(clippy::needless_pass_by_value)]
+        pub fn type_<T: InputType>(self, value: T) -> input<'html, Tag, T::Kind> {
+            let this = self.into_tag();
+            if !value.is_unset() {
+                this.html.write_attr_key("type");
+                value.write(this.html);
+            }
+            input {
+                html: this.html,
+                state: PhantomData,
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Deferred values in [`Html`], filled after rendering.
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use html_escape::{encode_double_quoted_attribute, encode_text};
+
+use crate::attributes::ToAttribute;
+use crate::{Html, ToHtml};
+
+/// A hole in [`Html`] whose value is only known after rendering, filled
+/// using [`Html::fill`].
+///
+/// This allows rendering the structure of a page first and splicing in data,
+/// e.g., from multiple async sources, later, without rendering the whole
+/// template again. A placeholder can be used as content and as attribute
+/// value, the value is escaped accordingly.
+///
+/// ```
+/// # use htmx::{html, Html, Placeholder};
+/// let user = Placeholder::new();
+/// let mut html = Html::from(html! {
+///     <a href=user>"Profile of "{user}</a>
+/// });
+/// html.fill(user, "<Ferris>");
+/// assert_eq!(
+///     html.body_only(),
+///     r#"<a href="&lt;Ferris&gt;">Profile of &lt;Ferris&gt;</a>"#
+/// );
+/// ```
+///
+/// Placeholders that are never filled render as empty.
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Placeholder(usize);
+
+impl Placeholder {
+    /// Creates a new, unique placeholder.
+    pub fn new() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(self, html: &mut Html, attribute: bool) {
+        // A sink already received the output around the hole.
+        if html.sink.is_set() {
+            return;
+        }
+        html.holes.push(Hole {
+            offset: html.buf.len(),
+            placeholder: self.0,
+            attribute,
+        });
+    }
+}
+
+impl Default for Placeholder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToHtml for Placeholder {
+    fn to_html(&self, html: &mut Html) {
+        self.record(html, false);
+    }
+}
+
+impl<T> ToAttribute<T> for Placeholder {
+    fn write(&self, html: &mut Html) {
+        html.write_str("=\"");
+        self.record(html, true);
+        html.write_quote();
+    }
+
+    fn write_inner(&self, html: &mut Html) {
+        self.record(html, true);
+    }
+}
+
+/// Position of a [`Placeholder`] in the rendered HTML.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Hole {
+    pub(crate) offset: usize,
+    placeholder: usize,
+    attribute: bool,
+}
+
+impl Hole {
+    pub(crate) fn moved_to(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+}
+
+impl Html {
+    /// Fills all occurrences of `placeholder` with `value`, escaped for the
+    /// content or attribute value it is used in.
+    ///
+    /// Fills need to happen before the HTML is converted, e.g., using
+    /// [`into_string`](Self::into_string) or by returning it from an
+    /// endpoint, afterwards unfilled placeholders are empty.
+    pub fn fill(&mut self, placeholder: Placeholder, value: impl Display) {
+        let value = value.to_string();
+        for index in 0..self.holes.len() {
+            let hole = self.holes[index];
+            if hole.placeholder != placeholder.0 {
+                continue;
+            }
+            let value = if hole.attribute {
+                encode_double_quoted_attribute(&value)
+            } else {
+                encode_text(&value)
+            };
+            self.buf.insert_str(hole.offset, &value);
+            for boundary in &mut self.boundaries {
+                if *boundary > hole.offset {
+                    *boundary += value.len();
+                }
+            }
+            // Holes are ordered, so later ones at the same offset end up
+            // behind the inserted value.
+            for later in &mut self.holes[index + 1..] {
+                later.offset += value.len();
+            }
+        }
+        self.holes.retain(|hole| hole.placeholder != placeholder.0);
+    }
+}
@@ -0,0 +1,218 @@
+//! Indented rendering of [`Html`] for debugging.
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+
+use crate::Html;
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+// Elements whose content is emitted unchanged, as whitespace is significant
+// or the content is not HTML.
+pub(crate) const RAW_ELEMENTS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// Indented rendering of [`Html`], created by [`Html::pretty`].
+///
+/// Puts every tag on its own line, indented by its depth. Elements only
+/// containing text, as well as the contents of `<pre>`, `<textarea>`,
+/// `<script>` and `<style>`, stay on a single line. Text is never modified,
+/// but as whitespace is inserted between tags, this is only meant for
+/// debugging, not for serving.
+///
+/// ```
+/// # use htmx::{html, Html};
+/// let html = Html::from(html! {
+///     <ul>
+///         <li>"Item"</li>
+///         <li><pre>"  code"</pre></li>
+///     </ul>
+/// });
+/// assert_eq!(
+///     html.pretty().to_string(),
+///     "<!DOCTYPE html>
+/// <ul>
+///     <li>Item</li>
+///     <li>
+///         <pre>  code</pre>
+///     </li>
+/// </ul>"
+/// );
+/// ```
+pub struct PrettyHtml<'a>(&'a str);
+
+impl Html {
+    /// Renders the HTML indented, see [`PrettyHtml`].
+    #[must_use]
+    pub fn pretty(&self) -> PrettyHtml<'_> {
+        PrettyHtml(&self.buf)
+    }
+}
+
+impl Display for PrettyHtml<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tokens = Tokens::new(self.0).peekable();
+        let mut lines = Lines { f, first: true };
+        let mut depth = 0;
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Open(name, tag) if !is_void(name) => {
+                    lines.line(depth, tag)?;
+                    if !lines.close_inline(depth, &mut tokens)? {
+                        depth += 1;
+                    }
+                }
+                Token::Close(tag) => {
+                    depth = depth.saturating_sub(1);
+                    lines.line(depth, tag)?;
+                }
+                Token::Open(_, tag) | Token::Other(tag) | Token::Text(tag) => {
+                    lines.line(depth, tag)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Lines<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    first: bool,
+}
+
+impl Lines<'_, '_> {
+    fn line(&mut self, depth: usize, content: &str) -> fmt::Result {
+        if !self.first {
+            self.f.write_str("\n")?;
+        }
+        self.first = false;
+        for _ in 0..depth {
+            self.f.write_str("    ")?;
+        }
+        self.f.write_str(content)
+    }
+
+    /// Writes text content and the close tag on the current line, if the
+    /// element contains nothing else, returning whether it did.
+    fn close_inline<'t>(
+        &mut self,
+        depth: usize,
+        tokens: &mut Peekable<impl Iterator<Item = Token<'t>>>,
+    ) -> Result<bool, fmt::Error> {
+        let text = tokens.next_if(|token| matches!(token, Token::Text(_)));
+        if let Some(Token::Close(tag)) = tokens.next_if(|token| matches!(token, Token::Close(_))) {
+            if let Some(Token::Text(text)) = text {
+                self.f.write_str(text)?;
+            }
+            self.f.write_str(tag)?;
+            Ok(true)
+        } else {
+            if let Some(Token::Text(text)) = text {
+                self.line(depth + 1, text)?;
+            }
+            Ok(false)
+        }
+    }
+}
+
+fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS
+        .iter()
+        .any(|void| void.eq_ignore_ascii_case(name))
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Token<'a> {
+    /// Open tag, with the element's name.
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    /// Self-closing tags, comments and doctypes.
+    Other(&'a str),
+    Text(&'a str),
+}
+
+impl<'a> Token<'a> {
+    pub(crate) fn as_str(&self) -> &'a str {
+        match self {
+            Token::Open(_, tag) | Token::Close(tag) | Token::Other(tag) | Token::Text(tag) => tag,
+        }
+    }
+}
+
+pub(crate) struct Tokens<'a> {
+    rest: &'a str,
+    /// Name of the raw element whose content comes next.
+    raw: Option<&'a str>,
+}
+
+impl<'a> Tokens<'a> {
+    pub(crate) fn new(html: &'a str) -> Self {
+        Self {
+            rest: html,
+            raw: None,
+        }
+    }
+
+    fn split(&mut self, at: usize) -> &'a str {
+        let (token, rest) = self.rest.split_at(at);
+        self.rest = rest;
+        token
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        if let Some(name) = self.raw.take() {
+            let close = format!("</{name}");
+            let end = self
+                .rest
+                .to_ascii_lowercase()
+                .find(&close.to_ascii_lowercase())
+                .unwrap_or(self.rest.len());
+            if end > 0 {
+                return Some(Token::Text(self.split(end)));
+            }
+        }
+        if !self.rest.starts_with('<') {
+            let end = self.rest.find('<').unwrap_or(self.rest.len());
+            return Some(Token::Text(self.split(end)));
+        }
+        if self.rest.starts_with("<!--") {
+            let end = self.rest.find("-->").map_or(self.rest.len(), |end| end + 3);
+            return Some(Token::Other(self.split(end)));
+        }
+        let mut quoted = false;
+        let end = self
+            .rest
+            .char_indices()
+            .find(|&(_, c)| {
+                quoted ^= c == '"';
+                !quoted && c == '>'
+            })
+            .map_or(self.rest.len(), |(end, _)| end + 1);
+        let tag = self.split(end);
+        if tag.starts_with("</") {
+            return Some(Token::Close(tag));
+        }
+        if tag.starts_with("<!") || tag.ends_with("/>") {
+            return Some(Token::Other(tag));
+        }
+        let name = tag[1..]
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default();
+        if RAW_ELEMENTS
+            .iter()
+            .any(|raw| raw.eq_ignore_ascii_case(name))
+        {
+            self.raw = Some(name);
+        }
+        Some(Token::Open(name, tag))
+    }
+}
@@ -0,0 +1,118 @@
+//! [`HtmlResponse`], HTML with a custom status code and headers.
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use crate::Html;
+
+/// HTML with a custom status code and headers, e.g., htmx
+/// [response headers](https://htmx.org/reference/#response_headers).
+///
+/// Can be returned from endpoints like [`Html`], the `Content-Type` is always
+/// `text/html; charset=utf-8`.
+///
+/// ```
+/// # use htmx::{html, HtmlResponse};
+/// let response = HtmlResponse::new(html! { <p>"Invalid email"</p> }.into_fragment())
+///     .status(422)
+///     .header("HX-Retarget", "#errors");
+/// assert_eq!(response.status_code(), 422);
+/// let headers: Vec<_> = response.headers().collect();
+/// assert_eq!(headers, [("HX-Retarget", "#errors")]);
+/// ```
+#[must_use]
+pub struct HtmlResponse {
+    pub(crate) html: Html,
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(Cow<'static, str>, String)>,
+}
+
+impl HtmlResponse {
+    /// Creates a `200 OK` response, use [`Html::fragment`] or
+    /// [`Fragment::into_fragment`](crate::Fragment::into_fragment) for
+    /// partial responses, e.g., content swapped in by htmx.
+    pub fn new(html: impl Into<Html>) -> Self {
+        Self {
+            html: html.into(),
+            status: 200,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Sets the status code, e.g., `422` for invalid form data.
+    ///
+    /// # Panics
+    /// Panics if `status` is not a [valid status code](https://httpwg.org/specs/rfc9110.html#status.codes),
+    /// i.e., not in `100..=999`.
+    pub fn status(mut self, status: u16) -> Self {
+        assert!(
+            (100..=999).contains(&status),
+            "invalid status code `{status}`, https://httpwg.org/specs/rfc9110.html#status.codes"
+        );
+        self.status = status;
+        self
+    }
+
+    /// Adds a header, setting the same header multiple times sends all of
+    /// them.
+    ///
+    /// The value is sent as is, the typed headers set using
+    /// [`HtmlResponse::hx`] take care of encoding theirs.
+    ///
+    /// ```
+    /// # use htmx::{Html, HtmlResponse};
+    /// let response = HtmlResponse::new(Html::fragment()).header("HX-Push-Url", "false");
+    /// let headers: Vec<_> = response.headers().collect();
+    /// assert_eq!(headers, [("HX-Push-Url", "false")]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics on [invalid header names](https://httpwg.org/specs/rfc9110.html#fields.names),
+    /// `Content-Type`, as it is always set to HTML, or values containing
+    /// characters other than visible ASCII, space and tab, e.g., line breaks
+    /// or `ü`.
+    pub fn header(mut self, name: impl Into<Cow<'static, str>>, value: impl Display) -> Self {
+        let name = name.into();
+        assert!(
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)),
+            "invalid header name `{name}`, https://httpwg.org/specs/rfc9110.html#fields.names"
+        );
+        assert!(
+            !name.eq_ignore_ascii_case("Content-Type"),
+            "`Content-Type` of an `HtmlResponse` is always `text/html; charset=utf-8`"
+        );
+        let value = value.to_string();
+        assert!(
+            value.chars().all(|c| c == '\t' || matches!(c, ' '..='~')),
+            "invalid value `{value:?}` for header `{name}`, https://httpwg.org/specs/rfc9110.html#fields.values"
+        );
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Returns the status code.
+    #[must_use]
+    pub fn status_code(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the headers in the order they were added.
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_str()))
+    }
+
+    /// Returns the HTML body.
+    pub fn html(&self) -> &Html {
+        &self.html
+    }
+}
+
+impl From<Html> for HtmlResponse {
+    fn from(html: Html) -> Self {
+        Self::new(html)
+    }
+}
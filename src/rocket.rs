@@ -0,0 +1,54 @@
+//! ```no_run
+//! use htmx::{html, Fragment, Html};
+//! use rocket::{get, launch, routes};
+//!
+//! #[get("/")]
+//! fn index() -> Fragment<impl FnOnce(&mut Html)> {
+//!     html! { <h1>"Hello rocket"</h1> }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().mount("/", routes![index])
+//! }
+//! ```
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{self, Responder};
+use rocket::Request;
+
+use crate::{Css, Fragment, Html, HtmlResponse, HtmxSrc};
+
+impl<'r> Responder<'r, 'static> for Html {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (ContentType::HTML, self.into_string()).respond_to(request)
+    }
+}
+
+impl<'r, F: FnOnce(&mut Html)> Responder<'r, 'static> for Fragment<F> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        self.into_response_html().respond_to(request)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for HtmlResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.html.respond_to(request)?;
+        response.set_status(Status::new(self.status));
+        for (name, value) in self.headers {
+            response.adjoin_header(Header::new(name, value));
+        }
+        Ok(response)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Css<'static> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (ContentType::CSS, self.0.into_owned()).respond_to(request)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for HtmxSrc {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (ContentType::JavaScript, Self::HTMX_SRC).respond_to(request)
+    }
+}
@@ -0,0 +1,85 @@
+//! Sanitizing untrusted HTML using [`ammonia`].
+use std::borrow::Cow;
+
+use ammonia::Builder;
+
+use crate::{Html, ToHtml};
+
+/// Untrusted HTML, e.g., user provided rich text, that is sanitized using an
+/// allow-list of tags and attributes before being put into the output.
+///
+/// This sits between plain strings, which are fully escaped, and
+/// [`Trusted`](crate::Trusted), which is not escaped at all. By default
+/// [`ammonia`'s allow-list](Builder::default) is used, it can be adjusted
+/// with the builder methods, or replaced by [`Sanitized::with_builder`].
+///
+/// ```
+/// # use htmx::{html, Sanitized};
+/// let comment = r#"<b onclick="steal()">Hi</b> <a href="https://example.com">link</a><script>steal()</script>"#;
+/// assert_eq!(
+///     html! { <p>{Sanitized::new(comment)}</p> }.to_string(),
+///     r#"<!DOCTYPE html><p><b>Hi</b> <a href="https://example.com" rel="noopener noreferrer">link</a></p>"#
+/// );
+/// assert_eq!(
+///     html! { <p>{Sanitized::new(comment).tags(["b"])}</p> }.to_string(),
+///     "<!DOCTYPE html><p><b>Hi</b> link</p>"
+/// );
+/// ```
+pub struct Sanitized<'a> {
+    content: Cow<'a, str>,
+    builder: Builder<'a>,
+}
+
+impl<'a> Sanitized<'a> {
+    /// Sanitizes `content` using [`ammonia`'s default
+    /// allow-list](Builder::default).
+    pub fn new(content: impl Into<Cow<'a, str>>) -> Self {
+        Self::with_builder(content, Builder::default())
+    }
+
+    /// Sanitizes `content` using a custom [`Builder`].
+    pub fn with_builder(content: impl Into<Cow<'a, str>>, builder: Builder<'a>) -> Self {
+        Self {
+            content: content.into(),
+            builder,
+        }
+    }
+
+    /// Sets the allowed tags, replacing the default ones.
+    #[must_use]
+    pub fn tags(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        self.builder.tags(tags.into_iter().collect());
+        self
+    }
+
+    /// Allows additional tags.
+    #[must_use]
+    pub fn add_tags(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        self.builder.add_tags(tags);
+        self
+    }
+
+    /// Allows additional attributes on `tag`.
+    #[must_use]
+    pub fn add_tag_attributes(
+        mut self,
+        tag: &'a str,
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        self.builder.add_tag_attributes(tag, attributes);
+        self
+    }
+
+    /// Allows additional attributes on all tags.
+    #[must_use]
+    pub fn add_generic_attributes(mut self, attributes: impl IntoIterator<Item = &'a str>) -> Self {
+        self.builder.add_generic_attributes(attributes);
+        self
+    }
+}
+
+impl ToHtml for Sanitized<'_> {
+    fn to_html(&self, html: &mut Html) {
+        write!(html, "{}", self.builder.clean(&self.content));
+    }
+}
@@ -0,0 +1,265 @@
+//! Rendering into a [`WriteHtml`] instead of a buffer, see
+//! [`Html::with_sink`].
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::io;
+use std::sync::{Mutex, PoisonError};
+
+use forr::forr;
+
+use crate::{Html, WriteHtml};
+
+/// Object safe version of [`WriteHtml`], implemented by every sink.
+pub(crate) trait DynWriteHtml: Any + Send {
+    fn write_str(&mut self, s: &str);
+
+    fn write_char(&mut self, c: char);
+
+    fn write_fmt(&mut self, a: fmt::Arguments);
+
+    fn write_quote(&mut self);
+
+    fn write_gt(&mut self);
+
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded, write_attr_value_minified] $*
+        fn $method(&mut self, value: &dyn Display);
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<W: WriteHtml + Send + 'static> DynWriteHtml for W {
+    forr! { $method:ident in [write_attr_key, write_open_tag_unchecked, write_close_tag_unchecked, write_attr_value_unchecked, write_attr_value_inner_unchecked, write_attr_value_encoded, write_attr_value_inner_encoded, write_attr_value_minified] $*
+        fn $method(&mut self, value: &dyn Display) {
+            WriteHtml::$method(self, value);
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        WriteHtml::write_str(self, s);
+    }
+
+    fn write_char(&mut self, c: char) {
+        WriteHtml::write_char(self, c);
+    }
+
+    fn write_fmt(&mut self, a: fmt::Arguments) {
+        WriteHtml::write_fmt(self, a);
+    }
+
+    fn write_quote(&mut self) {
+        WriteHtml::write_quote(self);
+    }
+
+    fn write_gt(&mut self) {
+        WriteHtml::write_gt(self);
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// The sink of an [`Html`], set using [`Html::with_sink`].
+///
+/// It does not take part in comparisons and is not cloned, a clone buffers
+/// its output instead.
+pub(crate) struct Sink {
+    /// Only accessed through `&mut`, i.e., never locked, the `Mutex` keeps
+    /// [`Html`] `Sync` for sinks that are only `Send`.
+    writer: Option<Mutex<Box<dyn DynWriteHtml>>>,
+    /// Whether anything was written to `writer`.
+    written: bool,
+}
+
+impl Sink {
+    pub(crate) const fn none() -> Self {
+        Self {
+            writer: None,
+            written: false,
+        }
+    }
+
+    /// Returns the sink to write to, if there is one.
+    pub(crate) fn get(&mut self) -> Option<&mut dyn DynWriteHtml> {
+        let writer = self
+            .writer
+            .as_mut()?
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.written = true;
+        Some(&mut **writer)
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    pub(crate) fn written(&self) -> bool {
+        self.written
+    }
+}
+
+impl Clone for Sink {
+    fn clone(&self) -> Self {
+        Self::none()
+    }
+}
+
+impl PartialEq for Sink {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Sink {}
+
+impl PartialOrd for Sink {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Sink {
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Html {
+    /// Creates a piece of HTML that is written into `sink` while rendering,
+    /// instead of being buffered.
+    ///
+    /// Elements write their tags and attributes through the granular
+    /// [`WriteHtml`] methods, e.g.,
+    /// [`write_open_tag_unchecked`](WriteHtml::write_open_tag_unchecked), so
+    /// `sink` can observe or change them. Use [`into_sink`](Self::into_sink)
+    /// to get it back after rendering.
+    ///
+    /// Like [`Html::fragment`], it starts without a doctype. As everything is
+    /// passed on right away, the `Html` itself stays empty, [flush
+    /// boundaries](Html::flush) are ignored and [`Placeholder`]s stay empty.
+    ///
+    /// ```
+    /// # use std::fmt::{self, Display};
+    /// # use htmx::{html, Html, WriteHtml};
+    /// #[derive(Default)]
+    /// struct Outline {
+    ///     html: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// impl WriteHtml for Outline {
+    ///     fn write_str(&mut self, s: &str) {
+    ///         self.html.push_str(s);
+    ///     }
+    ///
+    ///     fn write_char(&mut self, c: char) {
+    ///         self.html.push(c);
+    ///     }
+    ///
+    ///     fn write_fmt(&mut self, a: fmt::Arguments) {
+    ///         fmt::Write::write_fmt(&mut self.html, a).unwrap();
+    ///     }
+    ///
+    ///     fn write_open_tag_unchecked(&mut self, name: impl Display) {
+    ///         self.tags.push(name.to_string());
+    ///         write!(self, "<{name}");
+    ///     }
+    /// }
+    ///
+    /// let mut html = Html::with_sink(Outline::default());
+    /// html! { <main><h1>"Title"</h1></main> }.into_html(&mut html);
+    /// let outline: Outline = html.into_sink().unwrap();
+    /// assert_eq!(outline.tags, ["main", "h1"]);
+    /// assert_eq!(outline.html, "<main><h1>Title</h1></main>");
+    /// ```
+    ///
+    /// [`Placeholder`]: crate::Placeholder
+    pub fn with_sink(sink: impl WriteHtml + Send + 'static) -> Self {
+        Self {
+            sink: Sink {
+                writer: Some(Mutex::new(Box::new(sink))),
+                written: false,
+            },
+            ..Self::empty()
+        }
+    }
+
+    /// Returns the sink set using [`Html::with_sink`], if it is a `W`.
+    #[must_use]
+    pub fn into_sink<W: WriteHtml + Send + 'static>(self) -> Option<W> {
+        let writer = self
+            .sink
+            .writer?
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        writer.into_any().downcast().ok().map(|writer| *writer)
+    }
+}
+
+/// Sink writing to any [`io::Write`], e.g., a file or socket, see
+/// [`Fragment::write_to`].
+///
+/// As [`WriteHtml`] cannot fail, the first error is stored and returned from
+/// [`IoWriter::finish`], everything written afterwards is discarded.
+///
+/// ```
+/// # use htmx::{html, Html, IoWriter};
+/// let mut html = Html::with_sink(IoWriter::new(Vec::new()));
+/// html! { <p>"Tom & Jerry"</p> }.into_html(&mut html);
+/// let writer: IoWriter<Vec<u8>> = html.into_sink().unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"<p>Tom &amp; Jerry</p>");
+/// ```
+///
+/// [`Fragment::write_to`]: crate::Fragment::write_to
+pub struct IoWriter<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    /// Creates a new `IoWriter` writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Flushes and returns the writer, or the first error that occurred
+    /// while writing.
+    ///
+    /// # Errors
+    /// Errors if any write to the underlying writer failed.
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.error {
+            Some(error) => Err(error),
+            None => {
+                self.writer.flush()?;
+                Ok(self.writer)
+            }
+        }
+    }
+
+    fn try_write(&mut self, write: impl FnOnce(&mut W) -> io::Result<()>) {
+        if self.error.is_none() {
+            self.error = write(&mut self.writer).err();
+        }
+    }
+}
+
+impl<W: io::Write> WriteHtml for IoWriter<W> {
+    fn write_str(&mut self, s: &str) {
+        self.try_write(|w| w.write_all(s.as_bytes()));
+    }
+
+    fn write_char(&mut self, c: char) {
+        self.try_write(|w| w.write_all(c.encode_utf8(&mut [0; 4]).as_bytes()));
+    }
+
+    fn write_fmt(&mut self, a: fmt::Arguments) {
+        self.try_write(|w| w.write_fmt(a));
+    }
+}
@@ -0,0 +1,116 @@
+//! Sending [`Html`] in parts, see [`HtmlStream`].
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::{Html, IntoHtml};
+
+/// A part of an [`HtmlStream`], rendered once the chunks before it were
+/// returned.
+type Part = Box<dyn FnOnce(&mut Html) + Send>;
+
+/// [`Html`] rendered in parts, every part is only rendered once the chunks
+/// before it were returned, i.e., when the consumer polls for more.
+///
+/// This allows sending, e.g., the `<head>` of a page, while the data for the
+/// `<body>` is still loading. Every part ends a chunk, [flush
+/// boundaries](Html::flush) split a part into further chunks. Content
+/// following an unfilled [`Placeholder`] is held back until a later part
+/// fills it, placeholders that are never filled are empty.
+///
+/// Returning it from an endpoint sends the chunks as they are rendered, it is
+/// also an [`Iterator`] over the chunks.
+///
+/// ```
+/// # use htmx::{html, HtmlStream};
+/// # fn load_comments() -> Vec<String> { vec!["First".into()] }
+/// let page = HtmlStream::new(html! {
+///     <head><title>"Comments"</title></head>
+/// })
+/// .then(html! {
+///     <body>
+///         for comment in load_comments() {
+///             <p>{comment}</p>
+///         }
+///     </body>
+/// });
+/// assert_eq!(page.collect::<Vec<_>>(), [
+///     "<!DOCTYPE html><head><title>Comments</title></head>",
+///     "<body><p>First</p></body>",
+/// ]);
+/// ```
+///
+/// [`Placeholder`]: crate::Placeholder
+#[must_use]
+pub struct HtmlStream {
+    /// Rendered content that was not returned yet.
+    html: Html,
+    /// Parts that were not rendered yet.
+    parts: VecDeque<Part>,
+}
+
+impl HtmlStream {
+    /// Creates a stream rendering `content`, starting with the
+    /// `<!DOCTYPE html>`, like [`Html::new`].
+    pub fn new(content: impl IntoHtml + Send + 'static) -> Self {
+        Self {
+            html: Html::new(),
+            parts: VecDeque::new(),
+        }
+        .then(content)
+    }
+
+    /// Adds `content` as a new part, it is only rendered once everything
+    /// before it was returned.
+    pub fn then(mut self, content: impl IntoHtml + Send + 'static) -> Self {
+        self.parts
+            .push_back(Box::new(move |html: &mut Html| content.into_html(html)));
+        self
+    }
+
+    /// Whether all chunks were returned.
+    #[cfg(feature = "axum")]
+    pub(crate) fn is_done(&self) -> bool {
+        self.parts.is_empty() && self.html.is_empty()
+    }
+}
+
+impl Iterator for HtmlStream {
+    type Item = String;
+
+    /// Returns the next chunk, rendering the next parts until one is
+    /// complete.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.html.next_complete_chunk() {
+                return Some(chunk);
+            }
+            let Some(part) = self.parts.pop_front() else {
+                // Nothing can fill the remaining placeholders anymore.
+                self.html.holes.clear();
+                return self.html.next_chunk();
+            };
+            part(&mut self.html);
+            self.html.flush();
+        }
+    }
+}
+
+impl Html {
+    /// Splits off everything before the first flush boundary, unless it
+    /// contains an unfilled placeholder.
+    fn next_complete_chunk(&mut self) -> Option<String> {
+        let end = *self.boundaries.front()?;
+        if self.holes.first().is_some_and(|hole| hole.offset < end) {
+            return None;
+        }
+        self.boundaries.pop_front();
+        let rest = self.buf.split_off(end);
+        for boundary in &mut self.boundaries {
+            *boundary -= end;
+        }
+        for hole in &mut self.holes {
+            *hole = hole.moved_to(hole.offset - end);
+        }
+        Some(mem::replace(&mut self.buf, rest))
+    }
+}
@@ -0,0 +1,206 @@
+//! Native SVG elements
+#![allow(non_camel_case_types, non_snake_case, clippy::return_self_not_must_use)]
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use forr::forr;
+
+use crate::attributes::{AttributeValue, Class, Number, Swap, Target, ToAttribute};
+use crate::{AttrState, ClassesAttr, ElementState, Fragment, Html, IntoHtml, Tag};
+
+// SVG attributes are case-sensitive, so the name is used unchanged, e.g.,
+// `viewBox`.
+macro_rules! svg_attribute {
+    ($ret:tt $name:ident) => {
+        svg_attribute!($ret $name<String>);
+    };
+    ($ret:tt $name:ident=$actual:tt) => {
+        svg_attribute!($ret $name=$actual<String>);
+    };
+    ($ret:tt $name:ident<Any>) => {
+        svg_attribute!($ret $name, stringify!($name), impl AttributeValue);
+    };
+    ($ret:tt $name:ident=$actual:tt<Any>) => {
+        svg_attribute!($ret $name, $actual, impl AttributeValue);
+    };
+    ($ret:tt $name:ident < $type:ty >) => {
+        svg_attribute!($ret $name, stringify!($name), impl ToAttribute<$type>);
+    };
+    ($ret:tt $name:ident=$actual:tt< $type:ty >) => {
+        svg_attribute!($ret $name, $actual, impl ToAttribute<$type>);
+    };
+    ($ret:tt $name:ident, $actual:expr, $type:ty) => {
+        attr_fn!($ret concat!("Sets the [`", $actual, "`](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/", $actual, ") attribute."), $name, $actual, $type);
+    };
+}
+
+// Attributes that take values
+forr! { ($type:ty, $attrs:tt) in [
+    (svg, [height<Any>, preserveAspectRatio, viewBox, width<Any>, x<Any>, xmlns, y<Any>]),
+    (symbol, [height<Any>, preserveAspectRatio, viewBox, width<Any>, x<Any>, y<Any>]),
+    (path, [d, pathLength<Number>]),
+    (circle, [cx<Any>, cy<Any>, pathLength<Number>, r<Any>]),
+    (ellipse, [cx<Any>, cy<Any>, pathLength<Number>, rx<Any>, ry<Any>]),
+    (line, [pathLength<Number>, x1<Any>, x2<Any>, y1<Any>, y2<Any>]),
+    (polygon, [pathLength<Number>, points]),
+    (polyline, [pathLength<Number>, points]),
+    (rect, [height<Any>, pathLength<Number>, rx<Any>, ry<Any>, width<Any>, x<Any>, y<Any>]),
+    (text, [dx<Any>, dy<Any>, lengthAdjust/*spacing|spacingAndGlyphs*/, rotate, textLength<Any>, text_anchor="text-anchor"/*start|middle|end*/, x<Any>, y<Any>]),
+    (tspan, [dx<Any>, dy<Any>, lengthAdjust/*spacing|spacingAndGlyphs*/, rotate, textLength<Any>, text_anchor="text-anchor"/*start|middle|end*/, x<Any>, y<Any>]),
+    (image, [height<Any>, href, preserveAspectRatio, width<Any>, x<Any>, y<Any>]),
+    (marker, [markerHeight<Any>, markerUnits/*userSpaceOnUse|strokeWidth*/, markerWidth<Any>, orient, preserveAspectRatio, refX<Any>, refY<Any>, viewBox]),
+    (mask, [height<Any>, maskContentUnits/*userSpaceOnUse|objectBoundingBox*/, maskUnits/*userSpaceOnUse|objectBoundingBox*/, width<Any>, x<Any>, y<Any>]),
+    (stop, [offset<Any>, stop_color="stop-color", stop_opacity="stop-opacity"<Any>])
+] $*
+    impl<'html, Attr: AttrState> $type<'html, Attr> {
+        forr! { $attr:ty in $attrs $*
+            svg_attribute!([$type<'html, Tag>] $attr);
+        }
+    }
+}
+
+forr! { $type:ty in [circle, defs, ellipse, g, image, line, marker, mask, path, polygon, polyline, rect, stop, svg, symbol, text, tspan] $*
+
+    #[doc = concat!("The [`<", stringify!($type), ">`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/", stringify!($type), ") element.")]
+    pub struct $type<'html, Attr: ElementState> {
+        html: &'html mut Html,
+        state: PhantomData<Attr>
+    }
+
+    impl $type<'_, Tag> {
+        #[doc(hidden)]
+        pub fn unused() {}
+    }
+
+    impl<'html> $type<'html, Tag> {
+        pub fn new(html: &'html mut Html) -> Self {
+            html.write_open_tag_unchecked(stringify!($type));
+            Self {
+                html,
+                state: PhantomData
+            }
+        }
+    }
+
+    // Attributes can be set in any state with an open tag, pending `class`
+    // attributes are closed first.
+    impl<'html, Attr: AttrState> $type<'html, Attr> {
+        fn into_tag(self) -> $type<'html, Tag> {
+            Attr::close_attr(self.html);
+            $type {
+                html: self.html,
+                state: PhantomData
+            }
+        }
+
+        /// Sets a custom attribute.
+        ///
+        /// Useful for setting, e.g., `data-{key}`.
+        ///
+        /// # Panics
+        /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+        pub fn custom_attr(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag> {
+            assert!(!key.to_string().chars().any(|c| c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
+            self.custom_attr_unchecked(key, value)
+        }
+
+        /// Sets a custom attribute, without checking for valid keys.
+        ///
+        /// Useful for setting, e.g., `data-{key}`.
+        ///
+        /// Note: This function does contain the check for [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0) only in debug builds, failing to ensure valid keys can lead to broken HTML output.
+        pub fn custom_attr_unchecked(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag>
+        {
+            debug_assert!(!key.to_string().chars().any(|c| c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '\0' | '"' | '\'' | '>' | '/' | '=')), "invalid key `{key}`, https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0");
+            let this = self.into_tag();
+            if !value.is_unset() {
+                this.html.write_attr_key(key);
+                value.write(this.html);
+            }
+            this
+        }
+
+        /// Sets multiple custom attributes, e.g., forwarded from a component.
+        ///
+        /// In [`rtml!`](crate::rtml) this can be written as `..attrs`.
+        ///
+        /// # Panics
+        /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+        pub fn custom_attrs<K: Display, V: AttributeValue>(self, attrs: impl IntoIterator<Item = (K, V)>) -> $type<'html, Tag> {
+            let mut this = self.into_tag();
+            for (key, value) in attrs {
+                this = this.custom_attr(key, value);
+            }
+            this
+        }
+
+        /// Sets the `data-{key}` attribute, e.g., to pass values to JS.
+        ///
+        /// In [`html!`](crate::html) this can be written as `data_key=value` or
+        /// `data::key=value`, `_` are translated to `-`.
+        ///
+        /// # Panics
+        /// Panics on [invalid attribute names](https://www.w3.org/TR/2011/WD-html5-20110525/syntax.html#attributes-0).
+        pub fn data(self, key: impl Display, value: impl AttributeValue) -> $type<'html, Tag> {
+            self.custom_attr(format_args!("data-{key}"), value)
+        }
+
+        // Core and presentation attributes
+        forr! { $attr:ty in [
+            clip_path="clip-path", clip_rule="clip-rule"/*nonzero|evenodd*/, color, display, fill, fill_opacity="fill-opacity"<Any>, fill_rule="fill-rule"/*nonzero|evenodd*/, filter, id, lang, mask, opacity<Any>, stroke, stroke_dasharray="stroke-dasharray", stroke_dashoffset="stroke-dashoffset"<Any>, stroke_linecap="stroke-linecap"/*butt|round|square*/, stroke_linejoin="stroke-linejoin"/*arcs|bevel|miter|miter-clip|round*/, stroke_opacity="stroke-opacity"<Any>, stroke_width="stroke-width"<Any>, style, tabindex<Number>, transform, visibility/*visible|hidden|collapse*/] $*
+            svg_attribute!([$type<'html, Tag>] $attr);
+        }
+        // Typed htmx attributes, others are set via `custom_attr`
+        forr! { $attr:ty in [hx_swap="hx-swap"<Swap>, hx_target="hx-target"<Target>] $*
+            attribute!([$type<'html, Tag>] htmx|$attr);
+        }
+    }
+
+    impl<'html> $type<'html, Tag> {
+        /// Adds classes to the element.
+        ///
+        /// Can be called multiple times, all classes are merged into a single
+        /// `class` attribute, which is closed by setting any other attribute.
+        pub fn class(self, value: impl ToAttribute<Class>) -> $type<'html, ClassesAttr> {
+            ClassesAttr::open(self.html);
+            ClassesAttr::add(self.html, value);
+            $type {
+                html: self.html,
+                state: PhantomData
+            }
+        }
+    }
+
+    impl<'html> $type<'html, ClassesAttr> {
+        /// Adds classes to the element.
+        pub fn class(self, value: impl ToAttribute<Class>) -> Self {
+            ClassesAttr::add(self.html, value);
+            self
+        }
+
+        /// Closes the `class` attribute, allowing to set other attributes.
+        #[must_use]
+        pub fn close_attr(self) -> $type<'html, Tag> {
+            self.into_tag()
+        }
+    }
+
+    impl <Attr: ElementState> $type<'_, Attr> {
+        pub fn body(self, body: impl IntoHtml) -> impl IntoHtml {
+            Attr::close_tag(self.html);
+            body.into_html(self.html);
+            self.html.write_close_tag_unchecked(stringify!($type));
+            Fragment::EMPTY
+        }
+
+        #[must_use]
+        pub fn close(self) -> impl IntoHtml {
+            self.body(Fragment::EMPTY)
+        }
+    }
+}
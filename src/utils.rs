@@ -1,5 +1,9 @@
-use crate::attributes::ToAttribute;
-use crate::{html, Html, IntoHtml, ToHtml, ToScript};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use derive_more::Display;
+
+use crate::attributes::{AttributeValue, ToAttribute};
+use crate::{html, rtml, Html, IntoHtml, ToHtml, ToScript, DOCTYPE};
 
 /// Embed [HTMX script](https://htmx.org/).
 ///
@@ -14,11 +18,31 @@ pub struct HtmxSrc;
 impl HtmxSrc {
     /// HTMX source.
     pub const HTMX_SRC: &'static str = include_str!("htmx.min.js");
+    /// Version of the bundled [`HTMX_SRC`](Self::HTMX_SRC).
+    pub const VERSION: &'static str = "1.9.5";
 
     #[allow(clippy::new_ret_no_self)]
     pub fn new(_: &mut Html) -> ExprHtml<Self> {
         ExprHtml(Self)
     }
+
+    /// Loads HTMX from [unpkg](https://unpkg.com), instead of embedding it.
+    ///
+    /// This allows using a different version than the bundled
+    /// [`VERSION`](Self::VERSION), and lets browsers cache the script across
+    /// pages.
+    ///
+    /// ```
+    /// # use htmx::{html, HtmxSrc};
+    /// assert_eq!(
+    ///     html! { {HtmxSrc::cdn("1.9.10")} }.to_string(),
+    ///     r#"<!DOCTYPE html><script src="https://unpkg.com/htmx.org@1.9.10"></script>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn cdn(version: &str) -> impl IntoHtml + '_ {
+        html! { <script src=format!("https://unpkg.com/htmx.org@{version}")/> }
+    }
 }
 
 impl ToHtml for HtmxSrc {
@@ -33,6 +57,86 @@ impl ToScript for HtmxSrc {
     }
 }
 
+/// Marks a flush boundary, see [`Html::flush`].
+///
+/// ```
+/// # use htmx::{html, Flush, HtmlPage};
+/// html! {
+///     <HtmlPage title="Streamed">
+///         <Flush/>
+///         "content"
+///     </_>
+/// };
+/// ```
+#[must_use]
+#[derive(Clone, Copy)]
+pub struct Flush;
+
+impl Flush {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(_: &mut Html) -> ExprHtml<Self> {
+        ExprHtml(Self)
+    }
+}
+
+impl ToHtml for Flush {
+    fn to_html(&self, html: &mut Html) {
+        html.flush();
+    }
+}
+
+/// A unique element id, e.g., to reference an element as `hx-target`.
+///
+/// Using the same `Id` for the element's `id` and its
+/// [`selector`](Self::selector) guarantees that they match.
+///
+/// ```
+/// # use htmx::{html, Id};
+/// let results = Id::with_prefix("results");
+/// html! {
+///     <input name="q" hx::get="/search" hx::target=results.selector()/>
+///     <ul id=results/>
+/// };
+/// ```
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
+#[display("{prefix}-{index}")]
+pub struct Id {
+    prefix: &'static str,
+    index: usize,
+}
+
+impl Id {
+    /// Creates a new id, unique in this process, e.g., `htmx-4`.
+    pub fn new() -> Self {
+        Self::with_prefix("htmx")
+    }
+
+    /// Creates a new id with a readable `prefix`, e.g., `results-4`.
+    ///
+    /// The `prefix` should start with a letter and not contain whitespace to
+    /// be usable in a CSS selector.
+    pub fn with_prefix(prefix: &'static str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            prefix,
+            index: NEXT.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the CSS selector matching the element, i.e., `#{id}`.
+    #[must_use]
+    pub fn selector(&self) -> String {
+        format!("#{self}")
+    }
+}
+
+impl Default for Id {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[must_use]
 pub struct ExprHtml<T>(T);
 
@@ -53,6 +157,17 @@ impl<T, F: Into<T>, I: IntoIterator<Item = F>> From<I> for AttrVec<T> {
     }
 }
 
+/// Writes the doctype, when rendered into a [fragment](Html::fragment).
+struct Doctype;
+
+impl ToHtml for Doctype {
+    fn to_html(&self, html: &mut Html) {
+        if html.nothing_written() {
+            html.write_str(DOCTYPE);
+        }
+    }
+}
+
 #[crate::component]
 pub fn HtmlPage(
     /// Sets `<meta name="viewport">` to specify page supports mobile
@@ -66,28 +181,64 @@ pub fn HtmlPage(
     /// `<script src="{}">`
     #[default_type(std::iter::Empty<&'html str>)]
     scripts: impl IntoIterator<Item = impl ToAttribute<String>> + 'html,
+    /// `<script src="{}" defer>`
+    #[default_type(std::iter::Empty<&'html str>)]
+    deferred_scripts: impl IntoIterator<Item = impl ToAttribute<String>> + 'html,
+    /// `<script src="{}" type="module">`
+    #[default_type(std::iter::Empty<&'html str>)]
+    modules: impl IntoIterator<Item = impl ToAttribute<String>> + 'html,
+    /// `<link href="{0}" rel="preload" as="{1}">`, e.g.,
+    /// `("hero.webp", "image")`
+    #[default_type(std::iter::Empty<(&'html str, &'html str)>)]
+    preloads: impl IntoIterator<Item = (impl ToAttribute<String>, impl ToAttribute<String>)> + 'html,
+    /// `<link href="{}" rel="prefetch">`
+    #[default_type(std::iter::Empty<&'html str>)]
+    prefetches: impl IntoIterator<Item = impl ToAttribute<String>> + 'html,
     /// `<html lang="{lang}">`
     lang: Option<&'html str>,
+    /// Additional attributes of `<html>`, e.g., `("data-theme", "dark")`.
+    #[default_type(std::iter::Empty<(&'html str, &'html str)>)]
+    html_attrs: impl IntoIterator<Item = (impl Display, impl AttributeValue)> + 'html,
+    /// Additional attributes of `<body>`, e.g., `("hx-ext", "sse")` to enable
+    /// an htmx extension for the whole document.
+    #[default_type(std::iter::Empty<(&'html str, &'html str)>)]
+    body_attrs: impl IntoIterator<Item = (impl Display, impl AttributeValue)> + 'html,
+    /// Additional content of `<head>`, e.g., `<meta>` tags or a favicon,
+    /// rendered after the built-ins.
+    #[slot]
+    head: impl ::htmx::IntoHtml,
     body: impl ::htmx::IntoHtml + 'html,
 ) {
-    html!(
-        <html lang=lang>
-            <head>
-                <meta charset="utf-8"/>
-                <title>{title}</title>
-                if mobile {
-                    <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                }
-                for style_sheet in style_sheets {
-                    <link href=style_sheet rel="stylesheet"/>
-                }
-                for script in scripts {
-                    <script src=script/>
-                }
-            </head>
-            <body>
-                {body}
-            </body>
-        </html>
-    )
+    rtml! {
+        {Doctype},
+        html(lang: lang, ..html_attrs) [
+            head [
+                meta(charset: "utf-8"),
+                title [{title}],
+                if mobile [
+                    meta(name: "viewport", content: "width=device-width, initial-scale=1"),
+                ],
+                for (preload, kind) in preloads [
+                    link(href: preload, rel: "preload", as: kind),
+                ],
+                for style_sheet in style_sheets [
+                    link(href: style_sheet, rel: "stylesheet"),
+                ],
+                for script in scripts [
+                    script(src: script),
+                ],
+                for script in deferred_scripts [
+                    script(src: script, defer),
+                ],
+                for module in modules [
+                    script(src: module, type: "module"),
+                ],
+                for prefetch in prefetches [
+                    link(href: prefetch, rel: "prefetch"),
+                ],
+                {head},
+            ],
+            body(..body_attrs) [{body}],
+        ]
+    }
 }
@@ -0,0 +1,60 @@
+//! ```no_run
+//! use htmx::html;
+//! use warp::Filter;
+//!
+//! # async fn serve() {
+//! let index = warp::path::end().map(|| html! { <h1>"Hello warp"</h1> });
+//! warp::serve(index).run(([127, 0, 0, 1], 3030)).await;
+//! # }
+//! ```
+use warp::http::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use warp::http::StatusCode;
+use warp::reply::{self, Reply, Response};
+
+use crate::{Css, Fragment, Html, HtmlResponse, HtmxSrc};
+
+impl Reply for Html {
+    fn into_response(self) -> Response {
+        reply::html(self.into_string()).into_response()
+    }
+}
+
+impl<F: FnOnce(&mut Html) + Send> Reply for Fragment<F> {
+    fn into_response(self) -> Response {
+        self.into_response_html().into_response()
+    }
+}
+
+impl Reply for HtmlResponse {
+    fn into_response(self) -> Response {
+        let mut response = self.html.into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.status).expect("status is checked by `HtmlResponse::status`");
+        for (name, value) in self.headers {
+            response.headers_mut().append(
+                HeaderName::from_bytes(name.as_bytes())
+                    .expect("name is checked by `HtmlResponse::header`"),
+                HeaderValue::from_str(&value).expect("value is checked by `HtmlResponse::header`"),
+            );
+        }
+        response
+    }
+}
+
+impl Reply for Css<'static> {
+    fn into_response(self) -> Response {
+        reply::with_header(self.0.into_owned(), CONTENT_TYPE, "text/css; charset=utf-8")
+            .into_response()
+    }
+}
+
+impl Reply for HtmxSrc {
+    fn into_response(self) -> Response {
+        reply::with_header(
+            Self::HTMX_SRC,
+            CONTENT_TYPE,
+            "text/javascript; charset=utf-8",
+        )
+        .into_response()
+    }
+}
@@ -0,0 +1,120 @@
+use htmx::{css, html, Css};
+use insta::assert_snapshot;
+
+#[test]
+fn declarations() {
+    assert_snapshot!(
+        css! {
+            .btn {
+                color: red;
+                padding: 0 1 em;
+                margin: 0 -1px;
+                background-color: rgba(0, 0, 0, .5);
+                transition: opacity 0.5s ease-in-out;
+                -webkit-box-shadow: 0 0 1px #ccc !important;
+                transform: translate(-50%, -50%);
+                width: calc(100% - 2 em);
+                color: var(--main-color);
+            }
+        }
+        .0
+    );
+}
+
+#[test]
+fn selectors() {
+    assert_snapshot!(
+        css! {
+            a.active:hover, .card .title, ul > li::before {
+                font-weight: bold;
+            }
+            input[type="text"]:not(.wide), ".btn.primary" {
+                width: 10 em;
+            }
+        }
+        .0
+    );
+}
+
+#[test]
+fn interpolation() {
+    let color = "#fff";
+    let size = 2;
+    assert_snapshot!(
+        css! {
+            body {
+                color: {color};
+                font-size: {size}em;
+            }
+        }
+        .0
+    );
+}
+
+#[test]
+fn nesting() {
+    assert_snapshot!(
+        css! {
+            .card, .panel {
+                padding: 1 em;
+                & .title {
+                    font-weight: bold;
+                    &:hover, &.active {
+                        color: red;
+                    }
+                    .icon {
+                        width: 1 em;
+                    }
+                }
+                > p {
+                    margin: 0;
+                }
+                @media (max-width: 600px) {
+                    padding: 0;
+                    & .title {
+                        display: none;
+                    }
+                }
+            }
+        }
+        .0
+    );
+}
+
+#[test]
+fn at_rules() {
+    let breakpoint = 600;
+    assert_snapshot!(
+        css! {
+            @import "reset.css";
+            @media screen and (max-width: {breakpoint}px) {
+                .card {
+                    padding: 0;
+                }
+            }
+            @font-face {
+                font-family: "Open Sans";
+                src: url("/fonts/OpenSans.woff2");
+            }
+            @keyframes fade {
+                from {
+                    opacity: 0;
+                }
+                50% {
+                    opacity: 0.5;
+                }
+            }
+        }
+        .0
+    );
+}
+
+#[test]
+fn in_html() {
+    let style = css! { .card { color: red; } };
+    assert_snapshot!(html! {
+        {Css(".a{color:red}".into())}
+        <div>{&style}</div>
+    }
+    .to_string());
+}
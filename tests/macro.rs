@@ -4,9 +4,9 @@ use htmx_macros::component;
 
 macro_rules! assert_html {
     ($html:tt$(, $rtml:tt)?) => {
-        let html = html!$html;
+        let html = Html::from(html!$html);
         // $(assert_eq!(rtml!$rtml, html);)?
-        insta::assert_snapshot!(html.into_string());
+        insta::assert_snapshot!(html.body_only());
     };
 }
 
@@ -68,18 +68,125 @@ fn fn_component() {
     );
 }
 
+#[test]
+fn tag_component() {
+    #[component(tag = "section")]
+    fn Section(title: String, body: impl htmx::IntoHtml) {
+        html! {
+            <h2>{title}</h2>
+            {body}
+        }
+    }
+
+    assert_html!({
+        <Section title="Title" data-id="main"> <p>"Content"</p> </_>
+        <Section title="Empty"/>
+        <Section id="intro" class="wide" class="dark" style="margin:0" title="Global"/>
+        <Section title="Events" hidden aria-label="Events" onclick="a()" @dblclick="b()" on:keyup="c()" hx::swap=htmx::attributes::Swap::OuterHtml/>
+    });
+}
+
+#[test]
+fn lowercase_component() {
+    #[component]
+    fn Card(title: String) {
+        html! {
+            <article>{title}</article>
+        }
+    }
+
+    use Card as card;
+
+    assert_html!({
+        <card title="Aliased"/>
+        <card title="Closed"></_>
+    });
+}
+
+#[test]
+fn class_on_paths() {
+    #[component]
+    fn Badge(class: String, title: String) {
+        html! {
+            <span class=class>{title}</span>
+        }
+    }
+
+    use Badge as badge;
+
+    assert_html!({
+        <htmx::native::div class="a" id="b" class="c"/>
+        <badge class="new" title="Badge"/>
+    });
+}
+
+#[test]
+fn prop_into() {
+    #[component]
+    fn List(#[prop(into = false)] items: Vec<String>, title: String) {
+        html! {
+            <h2>{title}</h2>
+            <ul>
+                for item in items {
+                    <li>{item}</li>
+                }
+            </ul>
+        }
+    }
+
+    assert_html!({
+        <List title="List" items=(1..4).map(|i| i.to_string()).collect()/>
+    });
+}
+
 #[test]
 fn reserved_attributes() {
     assert_html!({
         <script type_="module" />
         <script async_=true />
-        // TODO <script type="module" />
-        // <script async=true />
+        <script type="module" />
+        <script async=true />
     }, {
         script(type_: "module"),
         script(async_: true),
-        // TODO script(type: "module"),
-        // script(async: true),
+        script(type: "module"),
+        script(async: true),
+    });
+}
+
+#[test]
+fn keyword_attributes() {
+    assert_html!({
+        <label for="name"> "Name" </label>
+        <input type="text" id="name"/>
+        <label r#for="name"/>
+    });
+}
+
+#[test]
+fn event_handlers() {
+    assert_html!({
+        <button @click="handler()"> "Click me" </button>
+        <div @mouseover="hover(this)"/>
+        <custom-element @click="handler()"/>
+    });
+}
+
+#[test]
+fn event_scripts() {
+    let count = 3;
+    let name = "\"quoted\"";
+    assert_html!({
+        <button on:click={ console.log($count, $name); }> "Log" </button>
+        <input on:input="update(this)" on:keydown={ event.preventDefault() }/>
+        <custom-element on:click={ alert($count) }/>
+    });
+}
+
+#[test]
+fn hx_on() {
+    assert_html!({
+        <button hx-on:click="alert(1)" on:click="log()"> "Click" </button>
     });
 }
 
@@ -93,6 +200,30 @@ fn custom_element() {
     });
 }
 
+#[test]
+fn computed_tag_name() {
+    enum Heading {
+        Page,
+        Section,
+    }
+
+    impl Heading {
+        fn tag_name(&self) -> &'static str {
+            match self {
+                Heading::Page => "h1",
+                Heading::Section => "h2",
+            }
+        }
+    }
+
+    let level = 3;
+    assert_html!({
+        <{Heading::Page.tag_name()} class="title"> "Page" </_>
+        <{Heading::Section.tag_name()}> "Section" </_>
+        <{format!("h{level}")} data-level=level/>
+    });
+}
+
 #[test]
 fn raw_html() {
     use htmx::RawSrc;
@@ -102,6 +233,30 @@ fn raw_html() {
     });
 }
 
+#[test]
+fn tuple() {
+    use htmx::RawSrc;
+    fn pieces() -> impl htmx::IntoHtml {
+        (
+            html! { <h1>"Title"</h1> },
+            "this < will be > escaped ",
+            RawSrc::new("This < will > not"),
+            1,
+            html! { <p>"Paragraph"</p> },
+            Some('!'),
+            None::<&str>,
+            "a",
+            "b",
+            "c",
+            "d",
+            "e",
+        )
+    }
+    assert_html!((
+        {pieces()}{("<h", 1)}
+    ));
+}
+
 #[test]
 fn controll_flow() {
     let mut b = [1, 2, 3].into_iter();
@@ -132,3 +287,457 @@ fn controll_flow() {
         ]
     });
 }
+
+#[test]
+fn nested_controll_flow() {
+    let mut items = ["a", "b", "c"].into_iter();
+    let mut rest = ["1", "2"].into_iter();
+    let rest = &mut rest;
+    assert_html!({
+        <ul>
+            while let Some(item) = items.next() {
+                <li>
+                    {item}
+                    if let Some(number) = rest.next() {
+                        <span>{number}</span>
+                    }
+                </li>
+            }
+        </ul>
+    });
+}
+
+#[test]
+fn literal_children() {
+    assert_html!({
+        <span>42</span>
+        <span>1.5</span>
+        <span>true</span>
+        <span>false</span>
+    });
+}
+
+#[test]
+fn js() {
+    let count = 3;
+    let script = htmx::js! {
+        console.log($count, "</script>");
+    };
+    assert_html!({
+        <script>{(script, htmx::RawSrc::new("console.log(\"raw\");"))}</script>
+    });
+}
+
+#[test]
+fn script_style_numbers() {
+    let width_px = 240;
+    let ratio = 0.5;
+    assert_html!({
+        <script>{("const limit = ", 42_u8, ", ratio = ", ratio, ";")}</script>
+        <style>{(".bar { width: ", width_px, "px; opacity: ", ratio, " }")}</style>
+        <script>{("const max = ", f64::INFINITY, ", min = ", f32::NEG_INFINITY, ", none = ", f64::NAN, ";")}</script>
+        <style>{(".top { z-index: ", f64::INFINITY, "; order: ", f32::NEG_INFINITY, "; opacity: ", f64::NAN, " }")}</style>
+    });
+}
+
+#[test]
+fn script_matches() {
+    let html = html! {
+        <script>
+            let text = match status { 200 => "ok", _ => "err" };
+        </script>
+        <script>
+            match code { 0 => console.log("none"), other => console.log(other) };
+        </script>
+    }
+    .into_string();
+    // Both scripts share the global scope, so their temporaries must not be
+    // redeclared using `let` or `const` at the top level.
+    assert!(!html.contains("let __match"), "{html}");
+    assert_eq!(html.matches("{ const __match0 =").count(), 2, "{html}");
+    assert!(
+        html.contains("else { const other = __match0 ; console . log ( other , ) ; }"),
+        "{html}"
+    );
+}
+
+#[test]
+fn element_ref() {
+    let html = html! {
+        <div ref=my_el> "Hello" </div>
+        <script>
+            document.getElementById($my_el).remove();
+        </script>
+    }
+    .into_string();
+    let id = html
+        .split_once("<div id=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(id, _)| id)
+        .expect("div should have an id");
+    assert!(id.starts_with("my_el-"), "{id}");
+    assert!(html.contains(&format!("getElementById ( \"{id}\"")));
+}
+
+#[test]
+fn element_ref_in_loop() {
+    let html = html! {
+        for item in ["a", "b"] {
+            <p ref=el> {item} </p>
+            <script> console.log($el); </script>
+        }
+    }
+    .into_string();
+    let ids: Vec<_> = html
+        .split("<p id=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('"'))
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(ids.len(), 2, "{html}");
+    assert_ne!(ids[0], ids[1]);
+    for id in ids {
+        assert!(
+            html.contains(&format!("console . log ( \"{id}\"")),
+            "{html}"
+        );
+    }
+}
+
+#[test]
+fn conditional_attributes() {
+    struct State {
+        disabled: bool,
+    }
+    impl State {
+        fn url(&self) -> Option<&str> {
+            Some("example.com")
+        }
+    }
+    let state = State { disabled: true };
+    let enabled = false;
+    let no_url: Option<&str> = None;
+    assert_html!({
+        <button disabled=?state.disabled> "Disabled" </button>
+        <button disabled=?enabled> "Enabled" </button>
+        <a href=?state.url()> "Link" </a>
+        <a href=?no_url download> "No Link" </a>
+        <custom-element data-url=?{no_url.or(Some("fallback"))}/>
+    });
+}
+
+#[test]
+fn shorthands_after_comparison() {
+    let (count, limit) = (1, 3);
+    assert_html!({
+        if count < limit {
+            <button @click="add()" on:focus={ console.log($count) } disabled=?{count > limit}> "Add" </button>
+        }
+        <p @click="reset()" hidden=?{count >= limit}> "Reset" </p>
+    });
+}
+
+#[test]
+fn namespaced_attributes() {
+    assert_html!({
+        <{"svg"} xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+            <{"use"} xlink:href="#icon"/>
+        </_>
+        <div xml:lang="en"/>
+    });
+}
+
+#[test]
+fn multiline_attributes() {
+    assert_html!({
+        <a
+            href="example.com"
+
+            download
+            target =
+                "_blank"
+        >
+            "Link"
+        </a>
+    });
+}
+
+#[test]
+fn debug() {
+    let html = Html::from(html! {
+        <div>
+            <p class="a"> "Hello" </p>
+            <br/>
+        </div>
+    });
+    insta::assert_snapshot!(format!("{html:?}"));
+}
+
+#[test]
+fn merged_classes() {
+    let active = true;
+    insta::assert_snapshot!(
+        html! {
+            <div class="a" id="merged" class="b" class={active.then_some("active")}/>
+            <p class={None::<&str>} title="no class"/>
+            <svg class="icon" class="small" viewBox="0 0 1 1"/>
+        }
+        .to_string()
+    );
+    insta::assert_snapshot!(
+        htmx::rtml! {
+            .a.b(id: "merged", class: "c"),
+            p(class: "x", title: "single")
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn conditional_classes() {
+    let enabled = false;
+    insta::assert_snapshot!(
+        html! {
+            <div class=[("a", true), ("b", false)]/>
+            <button class="btn" class=[("active", true), ("disabled", !enabled)]/>
+            <p class=[("hidden", false)] id="none"/>
+        }
+        .to_string()
+    );
+    let mut html = Html::new();
+    htmx::native::div::new(&mut html)
+        .class(("a", true))
+        .class(vec![("b", false), ("c", true)])
+        .close();
+    insta::assert_snapshot!(html.to_string());
+}
+
+#[test]
+fn class_then_attributes() {
+    let mut html = Html::new();
+    htmx::native::div::new(&mut html)
+        .class("a")
+        .class("b")
+        .id("c")
+        .custom_attr("title", "d")
+        .close();
+    assert_eq!(
+        html.body_only(),
+        r#"<div class="a b" id="c" title="d"></div>"#
+    );
+}
+
+#[test]
+fn let_else() {
+    let users = [Some("Alice"), None];
+    assert_html!({
+        for user in users {
+            <li>
+                let Some(name) = user else {
+                    <i>"unknown"</i>
+                };
+                <b>{name}</b>
+            </li>
+        }
+        // Only the rest of the loop body is skipped.
+        for user in users {
+            let Some(name) = user else {
+                "unknown"
+            };
+            <b>{name}</b>
+        }
+        <p>"after"</p>
+    });
+}
+
+#[test]
+fn loop_break() {
+    let mut pages = [vec!["a", "b"], vec![], vec!["c"]].into_iter();
+    assert_html!({
+        loop {
+            let Some(page) = pages.next() else {
+                break;
+            };
+            if page.is_empty() {
+                continue
+            }
+            <ul>
+                for item in page {
+                    <li>{item}</li>
+                }
+            </ul>
+        }
+    });
+}
+
+#[test]
+fn comments() {
+    assert_html!({
+        <!-- "[if IE]><p>Old browser</p><![endif]" -->
+        <!-- "--> -- ---" -->
+        <!-- "->" -->
+        {htmx::Comment::new("trailing -")}
+    });
+}
+
+#[test]
+fn collections() {
+    let names = vec!["Alice".to_string(), "<Bob>".to_string()];
+    let rows = [1, 2].map(|i| format!("row {i}"));
+    assert_html!({
+        <p>{&names}</p>
+        <p>{&names[..1]}</p>
+        <p>{rows}</p>
+        <ul>{htmx::List(names.iter().map(|name| html! { <li>{name}</li> }))}</ul>
+    });
+}
+
+#[test]
+fn primitives() {
+    let count = 3usize;
+    assert_html!({
+        <p>{count} " items, " {-1.5} " avg, " {true}</p>
+    });
+}
+
+#[test]
+fn data_attributes() {
+    assert_html!({
+        <div data_user_id=42 data::role="admin" data-raw="kept"/>
+        <web-component data_foo_bar="x"/>
+        <object data="movie.mp4" type="video/mp4"/>
+    });
+    let mut html = Html::new();
+    htmx::native::div::new(&mut html).data("count", 3).close();
+    insta::assert_snapshot!(html.to_string());
+    insta::assert_snapshot!(htmx::rtml! { div(data_user_id: 42) }.to_string());
+}
+
+#[test]
+fn rtml_flags_and_spread() {
+    let attrs = [("data-a", "1"), ("aria-label", "Close")];
+    insta::assert_snapshot!(htmx::rtml! {
+        div(hidden, "data-flag", id: "x", ..attrs),
+        button(disabled, formnovalidate: false)
+    }
+    .to_string());
+}
+
+#[test]
+fn rtml_attribute_keys() {
+    insta::assert_snapshot!(htmx::rtml! {
+        button(hx::post: "/clicked", hx::swap_oob: true, data_user_id: 1, type: "submit"),
+        "web-component"(some_attr: "a", flag)
+    }
+    .to_string());
+}
+
+#[test]
+fn rtml_function_call() {
+    fn item(label: String, count: i32) -> impl htmx::IntoHtml {
+        html! {
+            <li>{label}": "{count}</li>
+        }
+    }
+
+    insta::assert_snapshot!(htmx::rtml! {
+        ul[
+            <item("apples", 3)/>,
+            <item("pears", 0,)/>
+        ]
+    }
+    .to_string());
+}
+
+#[test]
+fn generic_component() {
+    #[component]
+    fn List<T: std::fmt::Display>(items: Vec<T>, #[default] ordered: bool) {
+        let items = items.iter().map(ToString::to_string);
+        html! {
+            <ul data_ordered=ordered>
+                for item in items {
+                    <li>{item}</li>
+                }
+            </ul>
+        }
+    }
+
+    insta::assert_snapshot!(
+        html! {
+            <List items=vec![1u32, 2, 3]/>
+            <List items=vec!["a", "b"] ordered/>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn result_component() {
+    #[component]
+    fn Price(amount: String) -> Result<(), String> {
+        let amount: u32 = amount
+            .parse()
+            .map_err(|_| format!("invalid amount `{amount}`"))?;
+        Ok(html! {
+            <span>{amount}" €"</span>
+        })
+    }
+
+    insta::assert_snapshot!(
+        html! {
+            <Price amount="12"/>
+            <Price amount="twelve"/>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn slots() {
+    #[component]
+    fn Layout(
+        #[slot] header: impl htmx::IntoHtml,
+        #[slot] footer: impl htmx::IntoHtml,
+        body: impl htmx::IntoHtml,
+    ) {
+        html! {
+            <header>{header}</header>
+            <main>{body}</main>
+            <footer>{footer}</footer>
+        }
+    }
+
+    let title = String::from("Title");
+    insta::assert_snapshot!(
+        html! {
+            <Layout header=html!{<h1>{&title}</h1>} footer="Footer">
+                "main"
+            </Layout>
+            <Layout>"no slots"</Layout>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn prop_name() {
+    #[component]
+    fn Field(
+        #[prop(name = "type")] kind: String,
+        #[prop(name = "for")] input: String,
+        #[prop(name = "label")] text: String,
+    ) {
+        html! {
+            <label for=&input>{text}</label>
+            <input type=kind id=input/>
+        }
+    }
+
+    insta::assert_snapshot!(
+        html! {
+            <Field type="email" for="mail" label="Mail"/>
+        }
+        .into_string()
+    );
+}
@@ -1,3 +1,6 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
 use chrono::{TimeZone, Utc};
 use htmx::{html, Html};
 
@@ -11,3 +14,126 @@ fn native() {
         .to_string()
     );
 }
+
+#[test]
+fn net_attributes() {
+    let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let socket = SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 8080);
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <div data-host=ip data-socket=socket/>
+            <a href=Ipv4Addr::new(192, 168, 0, 1)/>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn optional_number() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <canvas width=Some(100u32) height=&200u32/>
+            <canvas width={None::<u32>}/>
+            <div data-count={None::<u32>}/>
+            <custom-element data-count=Some(1u8) data-none={None::<u8>}/>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn number_formatting() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <canvas width=1e10_f64 height=1e-7_f32/>
+            <canvas width=u128::MAX height=i128::MIN/>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn typed_input() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <input type=input_type::Text name="user" maxlength=20 required/>
+            <input type=input_type::Number name="amount" min=0 max=10 step="any"/>
+            <input type=input_type::Checkbox name="remember" checked=false/>
+            <input type=input_type::Date name="day" min="2023-01-01"/>
+            <input type="range" step="5"/>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn svg() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <svg viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg" width=100>
+                <g stroke="black" stroke_width=0.5 hx::get="/shape">
+                    <path d="M 1 1 L 9 9" fill="none"/>
+                    <circle cx=5 cy=5 r="40%"/>
+                </g>
+                <text x=5 y=5 text_anchor="middle">"Label"</text>
+            </svg>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn aria() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <div role="dialog" aria_hidden=true aria_modal=false aria_labelledby="title">
+                <button aria_expanded=true aria_controls="menu" aria_checked="mixed"/>
+                <progress aria_valuenow=20 aria-label="custom"/>
+            </div>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn flag_or_value() {
+    insta::assert_snapshot!(
+        Html::from(html! {
+            <div contenteditable translate=false popover/>
+            <div contenteditable=false spellcheck=false translate=true/>
+            <div contenteditable="" translate="no" popover="manual"/>
+            <div contenteditable="plaintext-only" spellcheck hidden="until-found"/>
+            <audio crossorigin src="a.ogg"/>
+            <script crossorigin="use-credentials" src="a.js"/>
+        })
+        .to_string()
+    );
+}
+
+#[test]
+fn std_duration() {
+    assert_eq!(
+        html! {
+            <time datetime=Duration::from_secs(5400)/>
+            <time datetime=Duration::ZERO/>
+            <time datetime=Duration::from_millis(90_250)/>
+            <time datetime=Duration::from_nanos(3_600_000_000_001)/>
+        }
+        .into_fragment()
+        .to_string(),
+        r#"<time datetime="PT1H30M"></time><time datetime="PT0S"></time><time datetime="PT1M30.25S"></time><time datetime="PT1H"></time>"#
+    );
+}
+
+#[test]
+fn style_attribute() {
+    let compact = true;
+    insta::assert_snapshot!(html! {
+        <p style="color: red"/>
+        <p style=[("color", "red"), ("margin", "0")]/>
+        <p style=vec![("margin", compact.then_some("0")), ("padding", None)]/>
+        <p style=htmx::Declarations::new().set("content", "\"<quoted>\"")/>
+    }
+    .into_fragment()
+    .into_string());
+}
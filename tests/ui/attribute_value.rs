@@ -0,0 +1,8 @@
+use htmx::html;
+
+struct Count;
+
+fn main() {
+    html! { <canvas width=true/> };
+    html! { <div data_count=Count/> };
+}
@@ -0,0 +1,14 @@
+use htmx::html;
+
+fn main() {
+    html! {
+        for i in 0..3 {
+            <p>
+                if i == 1 {
+                    continue;
+                }
+                {i}
+            </p>
+        }
+    };
+}
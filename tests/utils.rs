@@ -1,4 +1,6 @@
-use htmx::{html, HtmlPage};
+use htmx::{
+    concat, html, write_escaped, Flush, Html, HtmlPage, Id, Placeholder, ToHtml, WriteHtml,
+};
 use insta::assert_snapshot;
 
 #[test]
@@ -14,3 +16,901 @@ fn html_page() {
         .as_str()
     )
 }
+
+#[test]
+fn amp() {
+    assert_snapshot!(Html::amp(
+        html! {
+            <title>"AMP"</title>
+            <link rel="canonical" href="https://example.com"/>
+        },
+        html! { <h1>"Hello AMP"</h1> }
+    )
+    .to_string());
+}
+
+#[test]
+fn email() {
+    assert_snapshot!(Html::email(html! { <p>"Hello Mail"</p> }).to_string());
+}
+
+#[test]
+fn with_capacity() {
+    let rows = || {
+        html! {
+            for i in 0..3 {
+                <tr><td>{i.to_string()}</td></tr>
+            }
+        }
+    };
+    let mut html = Html::with_capacity(1024);
+    html.reserve(64);
+    rows().into_html(&mut html);
+    assert_eq!(html.to_string(), rows().into_string());
+    assert_eq!(rows().into_string_with_capacity(1024), html.to_string());
+}
+
+#[test]
+fn post_process() {
+    assert_snapshot!(Html::from(html! {
+        <img src="/assets/logo.png"/>
+    })
+    .post_process(|html| *html = html.replace("/assets/", "/assets/v2/"))
+    .post_process(|html| html.push_str("<script src=\"/analytics.js\"></script>"))
+    .to_string());
+}
+
+#[test]
+fn post_process_boundaries() {
+    let html = || Html::from(html! { <p>"a"</p> <Flush/> <p>"b"</p> });
+    assert_eq!(
+        html()
+            .post_process(|html| *html = html.replace('a', "A"))
+            .split_at_boundary(),
+        ["<!DOCTYPE html><p>A</p>", "<p>b</p>"]
+    );
+    assert_eq!(
+        html()
+            .post_process(|html| *html = html.replace('a', "aa"))
+            .split_at_boundary(),
+        ["<!DOCTYPE html><p>aa</p><p>b</p>"]
+    );
+}
+
+#[test]
+fn escaped() {
+    let name = "Tom & Jerry";
+    let mut html = Html::new();
+    write_escaped!(html, "<b>{name}</b> {}", 1 < 2);
+    format_args!("<i>{name}</i>").to_html(&mut html);
+    assert_eq!(
+        html,
+        Html::from(html! {
+            {format!("<b>{name}</b> {}", 1 < 2)}
+            {format!("<i>{name}</i>")}
+        })
+    );
+}
+
+fn streamed() -> Html {
+    html! {
+        <HtmlPage title="Streamed">
+            <Flush/>
+            <p>"slow content"</p>
+        </_>
+    }
+    .into()
+}
+
+#[test]
+fn flush_boundary() {
+    assert_eq!(streamed().split_at_boundary(), [
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Streamed</title></head><body>",
+        "<p>slow content</p></body></html>"
+    ]);
+}
+
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "hyper"))]
+#[test]
+fn html_stream() {
+    let count = Placeholder::new();
+    let stream = htmx::HtmlStream::new(html! {
+        <head><title>"Comments"</title></head>
+    })
+    .then(html! {
+        <h1>{count}" comments"</h1>
+        <Flush/>
+        <p>"First"</p>
+    })
+    .then(htmx::Fragment(move |html: &mut Html| html.fill(count, 1)));
+    // The heading is held back until the count is filled.
+    assert_eq!(stream.collect::<Vec<_>>(), [
+        "<!DOCTYPE html><head><title>Comments</title></head>",
+        "<h1>1 comments</h1>",
+        "<p>First</p>"
+    ]);
+}
+
+/// Page whose body is only rendered once it is polled, `rendered` is set
+/// then.
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+fn lazy_page(rendered: std::sync::Arc<std::sync::atomic::AtomicBool>) -> htmx::HtmlStream {
+    struct Lazy(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+    impl ToHtml for Lazy {
+        fn to_html(&self, html: &mut Html) {
+            self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+            html! { <p>"slow content"</p> }.into_html(html);
+        }
+    }
+
+    let lazy = Lazy(rendered);
+    htmx::HtmlStream::new(html! { <head><title>"Streamed"</title></head> })
+        .then(html! { <body>{lazy}</body> })
+}
+
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+const LAZY_CHUNKS: [&str; 2] = [
+    "<!DOCTYPE html><head><title>Streamed</title></head>",
+    "<body><p>slow content</p></body>",
+];
+
+/// Polls `poll` until it is ready, parking the thread in between.
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+fn block_on<T>(mut poll: impl FnMut(&mut std::task::Context<'_>) -> std::task::Poll<T>) -> T {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match poll(&mut cx) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn actix_chunks() {
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use actix_web::body::MessageBody;
+    use actix_web::test::TestRequest;
+    use actix_web::Responder;
+
+    let request = TestRequest::default().to_http_request();
+    let mut body = pin!(streamed().respond_to(&request).into_body());
+    let mut chunks = Vec::new();
+    while let Some(chunk) = block_on(|cx| body.as_mut().poll_next(cx)) {
+        chunks.push(chunk.unwrap());
+    }
+    assert_eq!(chunks, streamed().split_at_boundary());
+
+    // The head is sent before the body is rendered.
+    let rendered = Arc::new(AtomicBool::new(false));
+    let mut body = pin!(lazy_page(rendered.clone()).respond_to(&request).into_body());
+    let head = block_on(|cx| body.as_mut().poll_next(cx)).unwrap().unwrap();
+    assert!(!rendered.load(Ordering::Relaxed));
+    let content = block_on(|cx| body.as_mut().poll_next(cx)).unwrap().unwrap();
+    assert!(rendered.load(Ordering::Relaxed));
+    assert!(block_on(|cx| body.as_mut().poll_next(cx)).is_none());
+    assert_eq!(vec![head, content], LAZY_CHUNKS);
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_chunks() {
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use axum_core::response::IntoResponse;
+    use http_body::Body;
+
+    let mut body = pin!(streamed().into_response().into_body());
+    let mut chunks = Vec::new();
+    while let Some(chunk) = block_on(|cx| body.as_mut().poll_data(cx)) {
+        chunks.push(chunk.unwrap());
+    }
+    assert_eq!(chunks, streamed().split_at_boundary());
+
+    // The head is sent before the body is rendered.
+    let rendered = Arc::new(AtomicBool::new(false));
+    let mut body = pin!(lazy_page(rendered.clone()).into_response().into_body());
+    let head = block_on(|cx| body.as_mut().poll_data(cx)).unwrap().unwrap();
+    assert!(!rendered.load(Ordering::Relaxed));
+    let content = block_on(|cx| body.as_mut().poll_data(cx)).unwrap().unwrap();
+    assert!(rendered.load(Ordering::Relaxed));
+    assert!(block_on(|cx| body.as_mut().poll_data(cx)).is_none());
+    assert_eq!(vec![head, content], LAZY_CHUNKS);
+}
+
+#[cfg(feature = "warp")]
+#[test]
+fn warp_reply() {
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use htmx::{Css, HtmxSrc};
+    use warp::hyper::body::HttpBody;
+    use warp::Reply;
+
+    let response = streamed().into_response();
+    assert_eq!(
+        response.headers()["Content-Type"],
+        "text/html; charset=utf-8"
+    );
+    let mut body = pin!(response.into_body());
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut bytes = Vec::new();
+    while let Poll::Ready(Some(chunk)) = body.as_mut().poll_data(&mut cx) {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(String::from_utf8(bytes).unwrap(), streamed().into_string());
+
+    assert_eq!(
+        Css("p{}".into()).into_response().headers()["Content-Type"],
+        "text/css; charset=utf-8"
+    );
+    assert_eq!(
+        HtmxSrc.into_response().headers()["Content-Type"],
+        "text/javascript; charset=utf-8"
+    );
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_routes {
+    use htmx::{html, Css, Fragment, Html, HtmlResponse, HtmxSrc};
+    use rocket::get;
+
+    #[get("/")]
+    pub fn index() -> Html {
+        super::streamed()
+    }
+
+    #[get("/fragment")]
+    pub fn fragment() -> Fragment<impl FnOnce(&mut Html)> {
+        html! { <p>"fragment"</p> }
+    }
+
+    #[get("/invalid")]
+    pub fn invalid() -> HtmlResponse {
+        HtmlResponse::new(Html::fragment())
+            .status(422)
+            .header("HX-Trigger", "invalid")
+            .header("HX-Trigger", "shake")
+    }
+
+    #[get("/style.css")]
+    pub fn style() -> Css<'static> {
+        Css("p{}".into())
+    }
+
+    #[get("/htmx.js")]
+    pub fn htmx_src() -> HtmxSrc {
+        HtmxSrc
+    }
+}
+
+#[cfg(feature = "rocket")]
+#[test]
+fn rocket_responders() {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket::routes;
+    use rocket_routes::*;
+
+    let client = Client::untracked(
+        rocket::build().mount("/", routes![index, fragment, invalid, style, htmx_src]),
+    )
+    .unwrap();
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.into_string().unwrap(), streamed().into_string());
+
+    let response = client.get("/fragment").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.into_string().unwrap(), "<p>fragment</p>");
+
+    let response = client.get("/invalid").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.headers().get("HX-Trigger").collect::<Vec<_>>(), [
+        "invalid", "shake"
+    ]);
+
+    let response = client.get("/style.css").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::CSS));
+    assert_eq!(response.into_string().unwrap(), "p{}");
+
+    let response = client.get("/htmx.js").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JavaScript));
+}
+
+#[cfg(feature = "sanitize")]
+#[test]
+fn sanitized() {
+    use htmx::Sanitized;
+
+    let content =
+        r#"<span class="hl" data-x="1" title="t" onclick="steal()">x</span><marquee>y</marquee>"#;
+    assert_eq!(
+        html! { {Sanitized::new(content)} }
+            .into_fragment()
+            .to_string(),
+        r#"<span title="t">x</span>y"#
+    );
+    assert_eq!(
+        html! {
+            {Sanitized::new(content)
+                .add_tags(["marquee"])
+                .add_tag_attributes("span", ["class"])
+                .add_generic_attributes(["data-x"])}
+        }
+        .into_fragment()
+        .to_string(),
+        r#"<span class="hl" data-x="1" title="t">x</span><marquee>y</marquee>"#
+    );
+    let mut builder = ammonia::Builder::empty();
+    builder.add_tags(["marquee"]);
+    assert_eq!(
+        html! { {Sanitized::with_builder(content, builder)} }
+            .into_fragment()
+            .to_string(),
+        "x<marquee>y</marquee>"
+    );
+}
+
+#[cfg(feature = "hyper")]
+#[test]
+fn hyper_chunks() {
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use hyper::body::HttpBody;
+    use hyper::{Body, Response};
+
+    let response: Response<Body> = streamed().into();
+    assert_eq!(
+        response.headers()["Content-Type"],
+        "text/html; charset=utf-8"
+    );
+    let mut body = pin!(response.into_body());
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut chunks = Vec::new();
+    while let Poll::Ready(Some(chunk)) = body.as_mut().poll_data(&mut cx) {
+        chunks.push(String::from_utf8(chunk.unwrap().to_vec()).unwrap());
+    }
+    assert_eq!(chunks, streamed().split_at_boundary());
+
+    let response: Response<Body> = html! { <p>"fragment"</p> }.into();
+    let mut body = pin!(response.into_body());
+    let Poll::Ready(Some(Ok(chunk))) = body.as_mut().poll_data(&mut cx) else {
+        panic!("fragment should be a single chunk");
+    };
+    assert_eq!(chunk, "<p>fragment</p>");
+    assert!(matches!(body.poll_data(&mut cx), Poll::Ready(None)));
+
+    // Full pages get a doctype, flush boundaries move with the content.
+    let response: Response<Body> = html! {
+        <html><head></head><Flush/><body>"page"</body></html>
+    }
+    .into();
+    let mut body = pin!(response.into_body());
+    let mut chunks = Vec::new();
+    while let Poll::Ready(Some(chunk)) = body.as_mut().poll_data(&mut cx) {
+        chunks.push(String::from_utf8(chunk.unwrap().to_vec()).unwrap());
+    }
+    assert_eq!(chunks, [
+        "<!DOCTYPE html><html><head></head>",
+        "<body>page</body></html>"
+    ]);
+}
+
+#[test]
+fn fragment() {
+    let item = || html! { <li>"Item"</li> };
+    assert_eq!(item().into_fragment().to_string(), "<li>Item</li>");
+    assert_eq!(Html::from(item()).as_str(), "<!DOCTYPE html><li>Item</li>");
+    assert_eq!(item().into_fragment().into_string(), "<li>Item</li>");
+    // Full pages keep their doctype when rendered as a fragment.
+    assert!(html! { <HtmlPage title="Page"/> }
+        .into_fragment()
+        .to_string()
+        .starts_with("<!DOCTYPE html><html>"));
+}
+
+#[test]
+fn pretty() {
+    assert_snapshot!(Html::from(html! {
+        <div class="card">
+            <h1>"Title"</h1>
+            <p>"Some " <b>"bold"</b> " text"</p>
+            <br/>
+            <textarea>"  keep\n  this"</textarea>
+            <script>{"if (a > b) {}"}</script>
+        </div>
+    })
+    .pretty()
+    .to_string());
+}
+
+#[test]
+fn nested_html() {
+    let mut section = Html::from(html! { <header>"Cached"</header> });
+    section.flush();
+    html! { <main>"Content"</main> }.into_html(&mut section);
+
+    let page = Html::from(html! {
+        <body>
+            {&section}
+            {Html::fragment()}
+        </body>
+    });
+    assert_eq!(page.split_at_boundary(), [
+        "<!DOCTYPE html><body><header>Cached</header>",
+        "<main>Content</main></body>"
+    ]);
+}
+
+#[test]
+fn result() {
+    let parse = |value: &str| value.parse::<u8>().map_err(|e| format!("<{e}>"));
+    assert_eq!(
+        html! {
+            <p>{parse("12")}</p>
+            <p>{parse("x")}</p>
+        }
+        .to_string(),
+        "<!DOCTYPE html><p>12</p><p>&lt;invalid digit found in string&gt;</p>"
+    );
+}
+
+#[test]
+fn minified() {
+    let mut html = Html::from(html! {
+        <head>
+            <script>{"if (a  &&  b) {\n    c();\n}"}</script>
+        </head>
+    });
+    html.flush();
+    html! {
+        <body>
+            <p title="  kept  ">"  Hello,\n\t  world!  "</p>
+            <textarea>"  line\n  line"</textarea>
+        </body>
+    }
+    .into_html(&mut html);
+    assert_eq!(html.minified().split_at_boundary(), [
+        "<!DOCTYPE html><head><script>if (a  &&  b) {\n    c();\n}</script></head>",
+        "<body><p title=\"  kept  \"> Hello, world! </p><textarea>  line\n  line</textarea></body>"
+    ]);
+}
+
+#[test]
+fn placeholder() {
+    let name = Placeholder::new();
+    let unused = Placeholder::new();
+    let mut html = Html::from(html! {
+        <head><title>{name}</title></head>
+    });
+    html.flush();
+    let nested = Html::from(html! {
+        <p class="card" class=name data_missing=unused>"  Hello,  "{name}"  !  "</p>
+    });
+    html! {
+        <body>{nested}{unused}</body>
+    }
+    .into_html(&mut html);
+    let mut html = html.minified();
+    html.fill(name, "Ferris & co");
+    assert_eq!(html.split_at_boundary(), [
+        "<!DOCTYPE html><head><title>Ferris &amp; co</title></head>",
+        "<body><p class=\"card Ferris &amp; co\" data-missing=\"\"> Hello, Ferris &amp; co ! \
+         </p></body>"
+    ]);
+}
+
+#[test]
+fn placeholder_class() {
+    let class = Placeholder::new();
+    let style = Placeholder::new();
+    let mut html = Html::from(html! {
+        <p class=class style=style>"only"</p>
+        <p class=class class="x">"first"</p>
+    });
+    html.fill(class, "card");
+    html.fill(style, "color: red");
+    assert_eq!(
+        html.body_only(),
+        "<p class=\"card\" style=\"color: red\">only</p><p class=\"card x\">first</p>"
+    );
+}
+
+#[test]
+fn concat_fragments() {
+    let mut sections = vec![html! { <h1>"Title"</h1> }.boxed()];
+    for item in ["a", "b"] {
+        sections.push(html! { <p>{item}</p> }.boxed());
+    }
+    let page = html! { <header>"Header"</header> }
+        .then(concat(sections))
+        .then(html! { <footer>"Footer"</footer> });
+    assert_eq!(
+        page.into_fragment().to_string(),
+        "<header>Header</header><h1>Title</h1><p>a</p><p>b</p><footer>Footer</footer>"
+    );
+}
+
+#[test]
+fn id() {
+    let results = Id::with_prefix("results");
+    assert_ne!(results, Id::with_prefix("results"));
+    assert_eq!(
+        html! {
+            <input hx::target=results.selector()/>
+            <ul id=results/>
+        }
+        .into_fragment()
+        .to_string(),
+        format!(r##"<input hx-target="#{results}"><ul id="{results}"></ul>"##)
+    );
+}
+
+#[test]
+fn derive_write_html() {
+    #[derive(WriteHtml)]
+    struct Named<'a> {
+        context: &'a str,
+        #[html]
+        buffer: &'a mut Html,
+    }
+
+    #[derive(WriteHtml)]
+    struct Tuple(u32, #[html] Html);
+
+    let mut buffer = Html::fragment();
+    let mut named = Named {
+        context: "named",
+        buffer: &mut buffer,
+    };
+    let context = named.context;
+    write_escaped!(named, "<{context}>");
+    assert_eq!(buffer.to_string(), "&lt;named&gt;");
+
+    let mut tuple = Tuple(1, Html::fragment());
+    let count = tuple.0;
+    write_escaped!(tuple, "{count}");
+    assert_eq!(tuple.1.to_string(), "1");
+}
+
+#[test]
+fn write_to() {
+    let out = html! {
+        <p>"Hello"</p>
+        <Flush/>
+        <p>"World"</p>
+    }
+    .write_to(Vec::new())
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "<!DOCTYPE html><p>Hello</p><p>World</p>"
+    );
+}
+
+#[test]
+fn sink() {
+    use std::fmt::{self, Display};
+
+    /// Uses the `data-` prefixed htmx attributes.
+    struct DataPrefixed(String);
+
+    impl WriteHtml for DataPrefixed {
+        fn write_str(&mut self, s: &str) {
+            self.0.push_str(s);
+        }
+
+        fn write_char(&mut self, c: char) {
+            self.0.push(c);
+        }
+
+        fn write_fmt(&mut self, a: fmt::Arguments) {
+            fmt::Write::write_fmt(&mut self.0, a).unwrap();
+        }
+
+        fn write_attr_key(&mut self, key: impl Display) {
+            let key = key.to_string();
+            if key.starts_with("hx-") {
+                write!(self, " data-{key}");
+            } else {
+                write!(self, " {key}");
+            }
+        }
+    }
+
+    let mut html = Html::with_sink(DataPrefixed(String::new()));
+    html! {
+        <button hx::get="/items" id="load" class="big">"Load"</button>
+    }
+    .into_html(&mut html);
+    assert_eq!(html.to_string(), "");
+    let DataPrefixed(output) = html.into_sink().unwrap();
+    assert_eq!(
+        output,
+        r#"<button data-hx-get="/items" id="load" class="big">Load</button>"#
+    );
+}
+
+#[test]
+fn nonce() {
+    let mut html = Html::with_nonce("n\"once");
+    html! {
+        <HtmlPage title="Nonce" scripts=["app.js"]>
+            <script>"init()"</script>
+            {htmx::Css("p{}".into())}
+        </_>
+    }
+    .into_html(&mut html);
+    assert_eq!(html.nonce(), Some("n\"once"));
+    assert_snapshot!(html.minified().to_string());
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_html_response() {
+    use axum_core::response::IntoResponse;
+    use htmx::HtmlResponse;
+
+    let response = HtmlResponse::new(html! { <p>"Invalid"</p> }.into_fragment())
+        .status(422)
+        .header("HX-Trigger", "invalid")
+        .header("HX-Trigger", "shake")
+        .into_response();
+    assert_eq!(response.status(), 422);
+    assert_eq!(
+        response.headers()["Content-Type"],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get_all("HX-Trigger")
+            .iter()
+            .collect::<Vec<_>>(),
+        ["invalid", "shake"]
+    );
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn actix_html_response() {
+    use actix_web::test::TestRequest;
+    use actix_web::Responder;
+    use htmx::HtmlResponse;
+
+    let response = HtmlResponse::new(Html::fragment())
+        .status(303)
+        .header("HX-Redirect", "/login")
+        .respond_to(&TestRequest::default().to_http_request());
+    assert_eq!(response.status(), 303);
+    assert_eq!(response.headers().get("HX-Redirect").unwrap(), "/login");
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+}
+
+#[cfg(feature = "warp")]
+#[test]
+fn warp_html_response() {
+    use htmx::HtmlResponse;
+    use warp::Reply;
+
+    let response = HtmlResponse::new(Html::fragment())
+        .status(286)
+        .header("HX-Refresh", "true")
+        .into_response();
+    assert_eq!(response.status(), 286);
+    assert_eq!(response.headers()["HX-Refresh"], "true");
+    assert_eq!(
+        response
+            .headers()
+            .get_all("Content-Type")
+            .iter()
+            .collect::<Vec<_>>(),
+        ["text/html; charset=utf-8"]
+    );
+}
+
+#[test]
+#[should_panic = "`Content-Type` of an `HtmlResponse` is always"]
+fn html_response_content_type() {
+    let _ = htmx::HtmlResponse::new(Html::fragment()).header("content-type", "text/plain");
+}
+
+#[test]
+#[should_panic = "invalid value `\"#a\\r\\nSet-Cookie: b\"` for header `HX-Retarget`"]
+fn html_response_header_value() {
+    let _ = htmx::HtmlResponse::new(Html::fragment()).header("HX-Retarget", "#a\r\nSet-Cookie: b");
+}
+
+#[test]
+fn htmx_headers() {
+    use htmx::htmx_headers::{HxHeader, HxRedirect, HxRefresh, HxRetarget, HxTrigger};
+
+    assert_eq!(HxTrigger::new("a").event("b").value(), "a, b");
+    assert_eq!(
+        HxTrigger::new("a").event("grüße").event("b, c").value(),
+        r#"{"a":null,"gr\u00fc\u00dfe":null,"b, c":null}"#
+    );
+    assert_eq!(
+        HxTrigger::new("saved")
+            .detail("notify", serde_json::json!({ "message": "Grüße 🦀" }))
+            .after_swap()
+            .value(),
+        r#"{"saved":null,"notify":{"message":"Gr\u00fc\u00dfe \ud83e\udd80"}}"#
+    );
+    let response = htmx::HtmlResponse::new(Html::fragment())
+        .hx(HxRedirect("/login"))
+        .hx(HxRefresh);
+    assert_eq!(response.headers().collect::<Vec<_>>(), [
+        ("HX-Redirect", "/login"),
+        ("HX-Refresh", "true")
+    ]);
+    assert_eq!(
+        HxRedirect("/grüße 🦀").value(),
+        "/gr%C3%BC%C3%9Fe%20%F0%9F%A6%80"
+    );
+    assert_eq!(HxRetarget("#grüße\n").value(), r"#gr\fc \df \a ");
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_htmx_request() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use axum_core::extract::FromRequestParts;
+    use htmx::htmx_headers::HtmxRequest;
+
+    let extract = |request: http::request::Builder| {
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+        let mut future = pin!(HtmxRequest::from_request_parts(&mut parts, &()));
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(Ok(htmx)) => htmx,
+            _ => unreachable!(),
+        }
+    };
+
+    assert_eq!(extract(http::Request::builder()), HtmxRequest::default());
+    let htmx = extract(
+        http::Request::builder()
+            .header("HX-Request", "true")
+            .header("HX-Target", "contacts")
+            .header("HX-Current-URL", "https://example.com/"),
+    );
+    assert!(htmx.is_partial());
+    assert!(!htmx.boosted);
+    assert_eq!(htmx.trigger, None);
+    assert_eq!(htmx.target.as_deref(), Some("contacts"));
+    assert_eq!(htmx.current_url.as_deref(), Some("https://example.com/"));
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn actix_htmx_request() {
+    use actix_web::test::TestRequest;
+    use actix_web::FromRequest;
+    use htmx::htmx_headers::HtmxRequest;
+
+    let extract = |request: TestRequest| {
+        HtmxRequest::extract(&request.to_http_request())
+            .into_inner()
+            .unwrap()
+    };
+
+    assert_eq!(extract(TestRequest::default()), HtmxRequest::default());
+    let htmx = extract(
+        TestRequest::default()
+            .insert_header(("HX-Request", "true"))
+            .insert_header(("HX-Boosted", "true"))
+            .insert_header(("HX-Trigger", "nav")),
+    );
+    assert!(htmx.request);
+    assert!(!htmx.is_partial());
+    assert_eq!(htmx.trigger.as_deref(), Some("nav"));
+}
+
+#[test]
+fn html_page_head() {
+    assert_snapshot!(
+        html! {
+            <HtmlPage title="Title" style_sheets=["style.css"] head=html! {
+                <meta property="og:title" content="Title"/>
+                <link rel="icon" href="favicon.ico"/>
+            }>
+                "Content"
+            </_>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn html_page_attrs() {
+    assert_snapshot!(
+        html! {
+            <HtmlPage lang="en" html_attrs=[("data-theme", "dark")]
+                body_attrs=[("class", "app"), ("hx-ext", "sse")]>
+                "Content"
+            </_>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn html_page_resources() {
+    assert_snapshot!(
+        html! {
+            <HtmlPage title="Resources" scripts=["legacy.js"] deferred_scripts=["htmx.js"]
+                modules=["app.js"] preloads=[("hero.webp", "image")] prefetches=["next.html"]>
+                "Content"
+            </_>
+        }
+        .into_string()
+    );
+}
+
+#[test]
+fn doctype() {
+    let page = || html! { <p>"Page"</p> };
+    let mut html = Html::with_doctype(None);
+    page().into_html(&mut html);
+    assert_eq!(html.to_string(), "<p>Page</p>");
+    let mut html = Html::with_doctype(Some("<!doctype html amp>"));
+    page().into_html(&mut html);
+    assert_eq!(html.to_string(), "<!doctype html amp><p>Page</p>");
+    assert_eq!(html.body_only(), "<p>Page</p>");
+}
+
+#[test]
+fn declarations() {
+    use htmx::Declarations;
+
+    let declarations = Declarations::new()
+        .set("color", "red")
+        .set("font-family", "\"Fira Sans\", sans-serif")
+        .set("color", "blue");
+    assert_eq!(declarations.iter().collect::<Vec<_>>(), [
+        ("color", "blue"),
+        ("font-family", "\"Fira Sans\", sans-serif")
+    ]);
+    assert_snapshot!(
+        html! {
+            <style>{("p{", &declarations, "}")}</style>
+            <x-el style=&declarations style=Declarations::new()/>
+        }
+        .into_fragment()
+        .into_string()
+    );
+}